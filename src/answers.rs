@@ -0,0 +1,143 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    io,
+    path::{Path, PathBuf},
+};
+
+use aoc::solution::Answer;
+use serde::{Deserialize, Serialize};
+
+use crate::files::{load_toml, save_toml};
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct DayAnswers {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part1: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part2: Option<String>,
+}
+
+/// Accepted answers for a year, stored as `[dayNN]` tables of `partN = "answer"`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    path: PathBuf,
+    days: BTreeMap<String, DayAnswers>,
+}
+
+/// How a solver's answer compares to the accepted one.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Missing,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail { expected } => write!(f, "FAIL, expected {}", expected),
+            Verdict::Missing => write!(f, "missing, no accepted answer"),
+        }
+    }
+}
+
+fn key(day: u8) -> String {
+    format!("day{:02}", day)
+}
+
+impl Answers {
+    pub fn path(dir: &Path, year: u32) -> PathBuf {
+        dir.join(format!("{year}.toml"))
+    }
+
+    /// Loads the answers at `path`, starting an empty set if the file does not exist yet.
+    pub fn open(path: &Path) -> io::Result<Self> {
+        Ok(Self {
+            path: path.to_owned(),
+            days: load_toml(path)?,
+        })
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<Answer> {
+        let answers = self.days.get(&key(day))?;
+        let answer = match part {
+            1 => answers.part1.as_deref(),
+            2 => answers.part2.as_deref(),
+            _ => None,
+        };
+        Some(answer?.parse().unwrap())
+    }
+
+    pub fn verdict(&self, day: u8, part: u8, answer: &Answer) -> Verdict {
+        match self.get(day, part) {
+            Some(expected) if expected == *answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+            },
+            None => Verdict::Missing,
+        }
+    }
+
+    /// Locks in an accepted answer and writes the file back.
+    pub fn insert(&mut self, day: u8, part: u8, answer: &Answer) -> io::Result<()> {
+        let answers = self.days.entry(key(day)).or_default();
+        let slot = match part {
+            1 => &mut answers.part1,
+            _ => &mut answers.part2,
+        };
+        *slot = Some(answer.to_string());
+        save_toml(&self.path, &self.days)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::files::temp_path;
+    use indoc::indoc;
+
+    const TEST_INPUT: &str = indoc! {r#"
+        [day05]
+        part1 = "744"
+        part2 = "347468726696961"
+
+        [day10]
+        part1 = "7"
+    "#};
+
+    fn answer(s: &str) -> Answer {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_verdict() {
+        let answers = Answers {
+            path: PathBuf::new(),
+            days: toml::from_str(TEST_INPUT).unwrap(),
+        };
+        assert_eq!(answers.verdict(5, 1, &answer("744")), Verdict::Pass);
+        assert_eq!(
+            answers.verdict(5, 2, &answer("14")),
+            Verdict::Fail {
+                expected: "347468726696961".to_owned()
+            }
+        );
+        assert_eq!(answers.verdict(10, 2, &answer("7")), Verdict::Missing);
+        assert_eq!(answers.verdict(3, 1, &answer("7")), Verdict::Missing);
+    }
+
+    #[test]
+    fn test_insert_round_trip() {
+        let path = temp_path("answers.toml");
+        let mut answers = Answers::open(&path).unwrap();
+        answers.insert(10, 1, &answer("7")).unwrap();
+        answers.insert(5, 2, &answer("347468726696961")).unwrap();
+        answers.insert(5, 1, &answer("744")).unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), TEST_INPUT);
+        assert_eq!(Answers::open(&path).unwrap(), answers);
+        std::fs::remove_file(path).unwrap();
+    }
+}
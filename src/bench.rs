@@ -0,0 +1,261 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    io,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use aoc::registry::Solver;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    files::{load_toml, save_toml},
+    runner::panic_message,
+};
+
+/// Timing statistics over the samples of one measurement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    /// Summarises the samples, which must not be empty.
+    pub fn new(mut samples: Vec<Duration>) -> Self {
+        samples.sort_unstable();
+        let rank = |p: usize| samples[(samples.len() * p).div_ceil(100).max(1) - 1];
+        Self {
+            min: samples[0],
+            median: rank(50),
+            p95: rank(95),
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "min {:?}, median {:?}, p95 {:?}",
+            self.min, self.median, self.p95
+        )
+    }
+}
+
+/// How often to run a part before and while measuring it.
+#[derive(Debug, Clone, Copy)]
+pub struct Plan {
+    pub warmup: usize,
+    pub samples: usize,
+}
+
+/// Generator and part timings of one solver.
+pub struct Bench {
+    pub day: u8,
+    pub part: u8,
+    pub generator: Stats,
+    pub solver: Stats,
+}
+
+fn measure<T>(plan: Plan, mut f: impl FnMut() -> Result<T, String>) -> Result<Stats, String> {
+    for _ in 0..plan.warmup {
+        f()?;
+    }
+    let mut samples = Vec::with_capacity(plan.samples);
+    for _ in 0..plan.samples.max(1) {
+        let start = Instant::now();
+        f()?;
+        samples.push(start.elapsed());
+    }
+    Ok(Stats::new(samples))
+}
+
+/// Times the generator and the part separately, each over its own warmup and samples.
+pub fn bench(solver: &Solver, input: &str, plan: Plan) -> Result<Bench, String> {
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        let generate = || solver.generate(input).map_err(|e| e.to_string());
+        let generator = measure(plan, generate)?;
+        let runner = generate()?;
+        let solver_stats = measure(plan, || runner.try_run().map_err(|e| e.to_string()))?;
+        Ok(Bench {
+            day: solver.day,
+            part: solver.part,
+            generator,
+            solver: solver_stats,
+        })
+    }))
+    .unwrap_or_else(|payload| Err(panic_message(payload)))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct Medians {
+    generator_ns: u64,
+    solve_ns: u64,
+}
+
+/// Median timings from an earlier run, stored as `[dayNN-partN]` tables.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Baseline {
+    path: PathBuf,
+    parts: BTreeMap<String, Medians>,
+}
+
+/// A median that got slower than the baseline by more than the threshold.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Regression {
+    pub measurement: &'static str,
+    pub baseline: Duration,
+    pub median: Duration,
+}
+
+impl Display for Regression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // a zero baseline has no meaningful relative change
+        if self.baseline.is_zero() {
+            return write!(
+                f,
+                "{} regressed: {:?} -> {:?}",
+                self.measurement, self.baseline, self.median
+            );
+        }
+        let change = self.median.as_secs_f64() / self.baseline.as_secs_f64() - 1.0;
+        write!(
+            f,
+            "{} regressed {:+.1}%: {:?} -> {:?}",
+            self.measurement,
+            change * 100.0,
+            self.baseline,
+            self.median
+        )
+    }
+}
+
+fn key(day: u8, part: u8) -> String {
+    format!("day{:02}-part{}", day, part)
+}
+
+impl Baseline {
+    pub fn path(dir: &Path, year: u32) -> PathBuf {
+        dir.join(format!("{year}-baseline.toml"))
+    }
+
+    /// Loads the baseline at `path`, starting an empty one if it does not exist yet.
+    pub fn open(path: &Path) -> io::Result<Self> {
+        Ok(Self {
+            path: path.to_owned(),
+            parts: load_toml(path)?,
+        })
+    }
+
+    /// Compares the medians against the baseline, allowing `threshold` percent slowdown.
+    pub fn regressions(&self, bench: &Bench, threshold: f64) -> Vec<Regression> {
+        let Some(baseline) = self.parts.get(&key(bench.day, bench.part)) else {
+            return Vec::new();
+        };
+        [
+            ("generator", baseline.generator_ns, bench.generator.median),
+            ("solver", baseline.solve_ns, bench.solver.median),
+        ]
+        .into_iter()
+        .map(|(measurement, baseline, median)| Regression {
+            measurement,
+            baseline: Duration::from_nanos(baseline),
+            median,
+        })
+        .filter(|r| r.median.as_secs_f64() > r.baseline.as_secs_f64() * (1.0 + threshold / 100.0))
+        .collect()
+    }
+
+    pub fn insert(&mut self, bench: &Bench) {
+        let medians = Medians {
+            generator_ns: bench.generator.median.as_nanos() as u64,
+            solve_ns: bench.solver.median.as_nanos() as u64,
+        };
+        self.parts.insert(key(bench.day, bench.part), medians);
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        save_toml(&self.path, &self.parts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::files::temp_path;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    fn bench(generator: u64, solver: u64) -> Bench {
+        let stats = |median| Stats {
+            min: ms(median),
+            median: ms(median),
+            p95: ms(median),
+        };
+        Bench {
+            day: 8,
+            part: 1,
+            generator: stats(generator),
+            solver: stats(solver),
+        }
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::new((1..=20).rev().map(ms).collect());
+        assert_eq!(
+            stats,
+            Stats {
+                min: ms(1),
+                median: ms(10),
+                p95: ms(19)
+            }
+        );
+        let stats = Stats::new(vec![ms(3)]);
+        assert_eq!((stats.min, stats.median, stats.p95), (ms(3), ms(3), ms(3)));
+    }
+
+    #[test]
+    fn test_regressions() {
+        let mut baseline = Baseline::default();
+        assert_eq!(baseline.regressions(&bench(10, 100), 10.0), vec![]);
+
+        baseline.insert(&bench(10, 100));
+        assert_eq!(baseline.regressions(&bench(11, 109), 10.0), vec![]);
+        assert_eq!(
+            baseline.regressions(&bench(10, 150), 10.0),
+            vec![Regression {
+                measurement: "solver",
+                baseline: ms(100),
+                median: ms(150)
+            }]
+        );
+    }
+
+    #[test]
+    fn test_regression_from_zero() {
+        let regression = Regression {
+            measurement: "solver",
+            baseline: Duration::ZERO,
+            median: ms(1),
+        };
+        assert_eq!(regression.to_string(), "solver regressed: 0ns -> 1ms");
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let path = temp_path("baseline.toml");
+        let mut baseline = Baseline::open(&path).unwrap();
+        baseline.insert(&bench(10, 100));
+        baseline.save().unwrap();
+
+        assert_eq!(Baseline::open(&path).unwrap(), baseline);
+        std::fs::remove_file(path).unwrap();
+    }
+}
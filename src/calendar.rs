@@ -0,0 +1,211 @@
+use std::fmt::Display;
+
+use jiff::{civil::Date, Zoned};
+
+/// The first year puzzles were published.
+pub const FIRST_YEAR: u32 = 2015;
+
+/// Puzzles unlock at midnight in this time zone.
+pub const UNLOCK_TZ: &str = "America/New_York";
+
+/// Events from 2025 onward run for 12 days instead of 25.
+pub fn last_day(year: u32) -> u8 {
+    if year >= 2025 {
+        12
+    } else {
+        25
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Puzzle {
+    pub year: u32,
+    pub day: u8,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DateError {
+    OutsideEvent(Date),
+    UnknownYear(u32),
+    UnknownDay { year: u32, day: u8 },
+}
+
+impl Display for DateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DateError::OutsideEvent(today) => write!(
+                f,
+                "{} ({}) is outside the event, pass the day explicitly",
+                today, UNLOCK_TZ
+            ),
+            DateError::UnknownYear(year) => write!(f, "there is no event in {}", year),
+            DateError::UnknownDay { year, day } => write!(
+                f,
+                "day {} is not part of {}, which runs for {} days",
+                day,
+                year,
+                last_day(*year)
+            ),
+        }
+    }
+}
+
+/// The date of the latest unlocked puzzle, which is the date at the unlock time zone.
+pub fn puzzle_date(now: &Zoned) -> Result<Date, jiff::Error> {
+    Ok(now.in_tz(UNLOCK_TZ)?.date())
+}
+
+/// Fills in the puzzle year and day left unspecified from `today`, see [`puzzle_date`].
+///
+/// Without a day, today has to be an event day. The year defaults to the latest event.
+pub fn resolve(year: Option<u32>, day: Option<u8>, today: Date) -> Result<Puzzle, DateError> {
+    let in_event = today.month() == 12;
+    let latest = if in_event {
+        today.year() as u32
+    } else {
+        today.year() as u32 - 1
+    };
+
+    let year = year.unwrap_or(latest);
+    if !(FIRST_YEAR..=latest).contains(&year) {
+        return Err(DateError::UnknownYear(year));
+    }
+
+    let day = match day {
+        Some(day) => day,
+        None if in_event && today.day() as u8 <= last_day(year) => today.day() as u8,
+        None => return Err(DateError::OutsideEvent(today)),
+    };
+    if !(1..=last_day(year)).contains(&day) {
+        return Err(DateError::UnknownDay { year, day });
+    }
+
+    Ok(Puzzle { year, day })
+}
+
+pub fn unlock_time(puzzle: Puzzle) -> Result<Zoned, jiff::Error> {
+    jiff::civil::date(puzzle.year as i16, 12, puzzle.day as i8)
+        .at(0, 0, 0, 0)
+        .in_tz(UNLOCK_TZ)
+}
+
+/// The first puzzle to unlock after `now`, and when it does.
+pub fn next_unlock(now: &Zoned) -> Result<(Puzzle, Zoned), jiff::Error> {
+    let today = puzzle_date(now)?;
+    let year = today.year() as u32;
+    let puzzle = if today.month() < 12 {
+        Puzzle { year, day: 1 }
+    } else if (today.day() as u8) < last_day(year) {
+        Puzzle {
+            year,
+            day: today.day() as u8 + 1,
+        }
+    } else {
+        Puzzle {
+            year: year + 1,
+            day: 1,
+        }
+    };
+    Ok((puzzle, unlock_time(puzzle)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jiff::civil::date;
+
+    fn utc(time: &str) -> Zoned {
+        time.parse::<jiff::Timestamp>()
+            .unwrap()
+            .in_tz("UTC")
+            .unwrap()
+    }
+
+    #[test]
+    fn test_puzzle_date() {
+        // still the 2nd in New York
+        assert_eq!(
+            puzzle_date(&utc("2025-12-03T04:59:59Z")).unwrap(),
+            date(2025, 12, 2)
+        );
+        assert_eq!(
+            puzzle_date(&utc("2025-12-03T05:00:00Z")).unwrap(),
+            date(2025, 12, 3)
+        );
+    }
+
+    #[test]
+    fn test_next_unlock() {
+        let (puzzle, at) = next_unlock(&utc("2025-12-03T04:59:59Z")).unwrap();
+        assert_eq!(puzzle, Puzzle { year: 2025, day: 3 });
+        assert_eq!(at.timestamp(), "2025-12-03T05:00:00Z".parse().unwrap());
+
+        let (puzzle, _) = next_unlock(&utc("2025-12-03T05:00:00Z")).unwrap();
+        assert_eq!(puzzle, Puzzle { year: 2025, day: 4 });
+
+        let (puzzle, at) = next_unlock(&utc("2026-07-14T12:00:00Z")).unwrap();
+        assert_eq!(puzzle, Puzzle { year: 2026, day: 1 });
+        assert_eq!(at.timestamp(), "2026-12-01T05:00:00Z".parse().unwrap());
+
+        let (puzzle, _) = next_unlock(&utc("2025-12-20T12:00:00Z")).unwrap();
+        assert_eq!(puzzle, Puzzle { year: 2026, day: 1 });
+        let (puzzle, _) = next_unlock(&utc("2024-12-24T12:00:00Z")).unwrap();
+        assert_eq!(
+            puzzle,
+            Puzzle {
+                year: 2024,
+                day: 25
+            }
+        );
+    }
+
+    #[test]
+    fn test_default_in_december() {
+        assert_eq!(
+            resolve(None, None, date(2025, 12, 7)),
+            Ok(Puzzle { year: 2025, day: 7 })
+        );
+        assert_eq!(
+            resolve(None, None, date(2025, 12, 13)),
+            Err(DateError::OutsideEvent(date(2025, 12, 13)))
+        );
+        assert_eq!(
+            resolve(None, None, date(2024, 12, 25)),
+            Ok(Puzzle {
+                year: 2024,
+                day: 25
+            })
+        );
+    }
+
+    #[test]
+    fn test_default_outside_december() {
+        assert_eq!(
+            resolve(None, None, date(2026, 7, 25)),
+            Err(DateError::OutsideEvent(date(2026, 7, 25)))
+        );
+        assert_eq!(
+            resolve(None, Some(3), date(2026, 7, 25)),
+            Ok(Puzzle { year: 2025, day: 3 })
+        );
+    }
+
+    #[test]
+    fn test_explicit_year() {
+        assert_eq!(
+            resolve(Some(2015), Some(25), date(2025, 12, 1)),
+            Ok(Puzzle {
+                year: 2015,
+                day: 25
+            })
+        );
+        assert_eq!(
+            resolve(Some(2014), Some(1), date(2025, 12, 1)),
+            Err(DateError::UnknownYear(2014))
+        );
+        assert_eq!(
+            resolve(Some(2026), Some(1), date(2026, 11, 30)),
+            Err(DateError::UnknownYear(2026))
+        );
+    }
+}
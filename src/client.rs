@@ -0,0 +1,202 @@
+use std::{
+    fmt::Display,
+    io,
+    path::{Path, PathBuf},
+};
+
+const USER_AGENT: &str = "github.com/meltinglava/aoc2025 scaffolder";
+
+#[derive(Debug)]
+pub enum ClientError {
+    Session(PathBuf, io::Error),
+    Http(ureq::Error),
+    Io(io::Error),
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::Session(path, e) => {
+                write!(f, "unable to read session from {}: {}", path.display(), e)
+            }
+            ClientError::Http(e) => write!(f, "request failed: {}", e),
+            ClientError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<ureq::Error> for ClientError {
+    fn from(e: ureq::Error) -> Self {
+        ClientError::Http(e)
+    }
+}
+
+impl From<io::Error> for ClientError {
+    fn from(e: io::Error) -> Self {
+        ClientError::Io(e)
+    }
+}
+
+/// Talks to the puzzle server, or anything answering on the same paths.
+pub struct Client {
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            session: session.trim().to_owned(),
+        }
+    }
+
+    /// Reads the session cookie value from `path`.
+    pub fn from_session_file(base_url: &str, path: &Path) -> Result<Self, ClientError> {
+        let session =
+            std::fs::read_to_string(path).map_err(|e| ClientError::Session(path.to_owned(), e))?;
+        Ok(Self::new(base_url, &session))
+    }
+
+    fn get(&self, path: &str) -> Result<String, ClientError> {
+        let body = ureq::get(format!("{}{}", self.base_url, path))
+            .header("Cookie", format!("session={}", self.session))
+            .header("User-Agent", USER_AGENT)
+            .call()?
+            .body_mut()
+            .read_to_string()?;
+        Ok(body)
+    }
+
+    pub fn input(&self, year: u32, day: u8) -> Result<String, ClientError> {
+        self.get(&format!("/{year}/day/{day}/input"))
+    }
+
+    /// The puzzle description page, with part 2 included once part 1 is solved.
+    pub fn puzzle(&self, year: u32, day: u8) -> Result<String, ClientError> {
+        self.get(&format!("/{year}/day/{day}"))
+    }
+
+    /// Posts an answer, returning the page the server responds with.
+    pub fn submit(
+        &self,
+        year: u32,
+        day: u8,
+        part: u8,
+        answer: &str,
+    ) -> Result<String, ClientError> {
+        let body = ureq::post(format!("{}/{year}/day/{day}/answer", self.base_url))
+            .header("Cookie", format!("session={}", self.session))
+            .header("User-Agent", USER_AGENT)
+            .send_form([("level", part.to_string().as_str()), ("answer", answer)])?
+            .body_mut()
+            .read_to_string()?;
+        Ok(body)
+    }
+}
+
+/// A minimal HTTP server standing in for the puzzle server in tests.
+#[cfg(test)]
+pub mod stand_in {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc::{self, Receiver},
+        thread::{self, JoinHandle},
+    };
+
+    pub struct Server {
+        pub url: String,
+        requests: Receiver<String>,
+        handle: JoinHandle<()>,
+    }
+
+    /// Serves `responses` in order, one per connection, then stops.
+    pub fn serve(responses: Vec<(u16, &'static str)>) -> Server {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, requests) = mpsc::channel();
+        let handle = thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                }
+                let mut payload = vec![0; content_length];
+                reader.read_exact(&mut payload).unwrap();
+                request.push_str(&String::from_utf8(payload).unwrap());
+                tx.send(request).unwrap();
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} Stand-in\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+        Server {
+            url,
+            requests,
+            handle,
+        }
+    }
+
+    impl Server {
+        /// Waits for the server to finish and returns every request it received.
+        pub fn requests(self) -> Vec<String> {
+            self.handle.join().unwrap();
+            self.requests.into_iter().collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_request() {
+        let server = stand_in::serve(vec![(200, "1\n2\n3\n")]);
+        let client = Client::new(&server.url, "abc123\n");
+        assert_eq!(client.input(2025, 3).unwrap(), "1\n2\n3\n");
+
+        let requests = server.requests();
+        assert!(requests[0].starts_with("GET /2025/day/3/input HTTP/1.1\r\n"));
+        assert!(requests[0].contains("session=abc123\r\n"));
+    }
+
+    #[test]
+    fn test_submit_request() {
+        let server = stand_in::serve(vec![(200, "<article>That's the right answer!</article>")]);
+        let client = Client::new(&server.url, "abc123");
+        let response = client.submit(2025, 3, 2, "357").unwrap();
+        assert!(response.contains("right answer"));
+
+        let requests = server.requests();
+        assert!(requests[0].starts_with("POST /2025/day/3/answer HTTP/1.1\r\n"));
+        assert!(requests[0].ends_with("\r\n\r\nlevel=2&answer=357"));
+    }
+
+    #[test]
+    fn test_error_status() {
+        let server = stand_in::serve(vec![(404, "Not Found")]);
+        let client = Client::new(&server.url, "abc123");
+        assert!(matches!(client.input(2025, 30), Err(ClientError::Http(_))));
+    }
+}
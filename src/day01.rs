@@ -1,30 +1,28 @@
-use nom::{
-    branch::alt,
-    character::complete::{self, newline},
-    combinator::value,
-    multi::separated_list1,
-    Parser,
-};
+use std::error::Error;
+
+use nom::{branch::alt, character::complete, combinator::value};
 
-use crate::convert_iresult_to_owned;
+use crate::{
+    parse::ParseError,
+    parse_all,
+    parsers::{lines, signed},
+    solution::{Answer, Solution},
+};
 
 type AocType = (i64, i64); // factor, value
 
 #[aoc_generator(day01)]
-pub fn input_generator(input: &str) -> Result<Vec<AocType>, nom::Err<nom::error::Error<String>>> {
-    let ret = separated_list1(
-        newline,
-        (
+pub fn input_generator(input: &str) -> Result<Vec<AocType>, ParseError> {
+    parse_all(
+        lines((
             alt((
                 value(-1i64, complete::char('L')),
                 value(1i64, complete::char('R')),
             )),
-            complete::i64,
-        ),
+            signed,
+        )),
+        input,
     )
-    .parse(input);
-
-    convert_iresult_to_owned(ret)
 }
 
 #[aoc(day01, part1)]
@@ -69,6 +67,24 @@ fn part2(input: &[AocType]) -> u64 {
         .sum()
 }
 
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<AocType>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(input_generator(input)?)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -92,6 +108,13 @@ mod tests {
         assert_eq!(part2(&input_generator(TEST_INPUT).unwrap()), 6);
     }
 
+    #[test]
+    fn test_stray_content() {
+        assert!(input_generator(&format!("{TEST_INPUT}\n")).is_err());
+        let e = input_generator(&TEST_INPUT.replace("L1\n", "L1 \n")).unwrap_err();
+        assert_eq!((e.line, e.column), (7, 3));
+    }
+
     #[test]
     fn test_crossing() {
         assert_eq!(normalizer(-50), -100);
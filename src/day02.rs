@@ -1,25 +1,17 @@
-use std::ops::RangeInclusive;
+use std::{error::Error, ops::RangeInclusive};
 
-use nom::{
-    character::complete, combinator::map, multi::separated_list1, sequence::separated_pair, Parser,
+use crate::{
+    parse::ParseError,
+    parse_all,
+    parsers::{comma_list, range},
+    solution::{Answer, Solution},
 };
 
-use crate::convert_iresult_to_owned;
-
 type AocType = RangeInclusive<usize>; // factor, value
 
 #[aoc_generator(day02)]
-pub fn input_generator(input: &str) -> Result<Vec<AocType>, nom::Err<nom::error::Error<String>>> {
-    let ret = separated_list1(
-        complete::char(','),
-        map(
-            separated_pair(complete::usize, complete::char('-'), complete::usize),
-            |(a, b)| a..=b,
-        ),
-    )
-    .parse(input);
-
-    convert_iresult_to_owned(ret)
+pub fn input_generator(input: &str) -> Result<Vec<AocType>, ParseError> {
+    parse_all(comma_list(range), input)
 }
 
 fn id_validator(input: &usize) -> bool {
@@ -70,6 +62,24 @@ fn part2(input: &[AocType]) -> usize {
         .sum()
 }
 
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<AocType>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(input_generator(input)?)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -90,6 +100,14 @@ mod tests {
         assert!(p2_id_validator(&1188511885));
     }
 
+    #[test]
+    fn test_stray_content() {
+        assert!(input_generator(&format!("{TEST_INPUT}\n")).is_ok());
+        let e = input_generator(&format!("{TEST_INPUT},")).unwrap_err();
+        assert_eq!(e.column, TEST_INPUT.len() + 1);
+        assert_eq!(e.expected, r#"the end of the input, found ",""#);
+    }
+
     #[test]
     fn test_p1() {
         assert_eq!(part1(&input_generator(TEST_INPUT).unwrap()), 1227775554)
@@ -1,32 +1,28 @@
-use nom::{
-    bytes::complete::take,
-    character::complete::{self, newline},
-    combinator::{all_consuming, map_parser, opt},
-    multi::{many1, separated_list1},
-    sequence::terminated,
-    Parser,
-};
+use std::error::Error;
+
+use nom::{bytes::complete::take, character::complete, combinator::map_parser, multi::many1};
 
-use crate::convert_iresult_to_owned;
+use crate::{
+    parse::ParseError,
+    parse_all,
+    parsers::lines,
+    solution::{Answer, Solution},
+};
 
 type AocType = Vec<usize>; // factor, value
 
 #[aoc_generator(day03)]
-pub fn input_generator(input: &str) -> Result<Vec<AocType>, nom::Err<nom::error::Error<String>>> {
-    let ret = all_consuming(terminated(
-        separated_list1(newline, many1(map_parser(take(1usize), complete::usize))),
-        opt(newline),
-    ))
-    .parse(input);
-
-    convert_iresult_to_owned(ret)
+pub fn input_generator(input: &str) -> Result<Vec<AocType>, ParseError> {
+    parse_all(
+        lines(many1(map_parser(take(1usize), complete::usize))),
+        input,
+    )
 }
 
 fn find_joltage(input: &[usize], size: usize) -> usize {
     let mut to_start = 0;
     let mut accumulator = 0;
-    let mut end = input.len() - size + 1;
-    for _index in 0..size {
+    for end in input.len() - size + 1..=input.len() {
         accumulator *= 10;
         let mut max_value = 0;
         for (i, battery) in input.iter().enumerate().take(end).skip(to_start) {
@@ -36,7 +32,6 @@ fn find_joltage(input: &[usize], size: usize) -> usize {
             }
         }
         accumulator += max_value;
-        end += 1;
     }
     accumulator
 }
@@ -51,6 +46,24 @@ fn part2(input: &[AocType]) -> usize {
     input.iter().map(|line| find_joltage(line, 12)).sum()
 }
 
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Vec<AocType>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(input_generator(input)?)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
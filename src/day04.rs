@@ -1,23 +1,26 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, error::Error};
 
-use crate::grid::Direction;
+use crate::{
+    grid::{Direction, Grid},
+    parse::ParseError,
+    solution::{Answer, Solution},
+};
 
-type AocType = (HashSet<(usize, usize)>, usize); // factor, value
+type AocType = (HashSet<(usize, usize)>, (usize, usize)); // paper, (width, height)
 
 #[aoc_generator(day04)]
-pub fn input_generator(input: &str) -> AocType {
-    let paper = input
-        .lines()
-        .enumerate()
-        .flat_map(|(line_nr, line)| {
-            line.chars()
-                .enumerate()
-                .filter(|&(_, c)| c == '@')
-                .map(move |(col_nr, _)| (col_nr, line_nr))
-        })
+pub fn input_generator(input: &str) -> Result<AocType, ParseError> {
+    let grid = Grid::parse(input, |c| match c {
+        '@' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })?;
+    let paper = grid
+        .iter()
+        .filter(|(_, &paper)| paper)
+        .map(|(pos, _)| pos)
         .collect();
-    let size = input.lines().next().unwrap().len();
-    (paper, size)
+    Ok((paper, grid.size()))
 }
 
 #[aoc(day04, part1)]
@@ -30,7 +33,11 @@ fn part1(input: &AocType) -> usize {
         .count()
 }
 
-fn forklift_accessable(pos: (usize, usize), input: &HashSet<(usize, usize)>, size: usize) -> usize {
+fn forklift_accessable(
+    pos: (usize, usize),
+    input: &HashSet<(usize, usize)>,
+    size: (usize, usize),
+) -> usize {
     Direction::all()
         .into_iter()
         .filter_map(|dir| dir.step(pos, size))
@@ -62,6 +69,24 @@ fn part2(input: &AocType) -> usize {
     removed
 }
 
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = AocType;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(input_generator(input)?)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -81,11 +106,18 @@ mod tests {
 
     #[test]
     fn test_p1() {
-        assert_eq!(part1(&input_generator(TEST_INPUT)), 13);
+        assert_eq!(part1(&input_generator(TEST_INPUT).unwrap()), 13);
+    }
+
+    #[test]
+    fn test_tall_grid() {
+        let input = input_generator("@@\n@@\n@@\n@@\n@@").unwrap();
+        assert_eq!(input.1, (2, 5));
+        assert_eq!(part1(&input), 4);
     }
 
     #[test]
     fn test_p2() {
-        assert_eq!(part2(&input_generator(TEST_INPUT)), 43);
+        assert_eq!(part2(&input_generator(TEST_INPUT).unwrap()), 43);
     }
 }
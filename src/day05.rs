@@ -1,35 +1,24 @@
-use nom::{
-    character::complete::{self, newline},
-    combinator::{all_consuming, map, opt},
-    multi::separated_list1,
-    sequence::{separated_pair, terminated},
-    Parser,
-};
-use std::ops::RangeInclusive;
+use nom::error::context;
+use std::{error::Error, ops::RangeInclusive};
 
-use crate::convert_iresult_to_owned;
+use crate::{
+    parse::ParseError,
+    parse_all,
+    parsers::{lines, number, range, section_pair},
+    solution::{Answer, Solution},
+};
 
 type AocType = (Vec<RangeInclusive<usize>>, Vec<usize>); // ranges
 
 #[aoc_generator(day05)]
-pub fn input_generator(input: &str) -> Result<AocType, nom::Err<nom::error::Error<String>>> {
-    let ret = all_consuming(terminated(
-        separated_pair(
-            separated_list1(
-                newline,
-                map(
-                    separated_pair(complete::usize, complete::char('-'), complete::usize),
-                    |(a, b)| a..=b,
-                ),
-            ),
-            (newline, newline),
-            separated_list1(newline, complete::usize),
+pub fn input_generator(input: &str) -> Result<AocType, ParseError> {
+    parse_all(
+        section_pair(
+            lines(range),
+            context("ingredient ids", lines(context("ingredient id", number))),
         ),
-        opt(newline),
-    ))
-    .parse(input);
-
-    convert_iresult_to_owned(ret)
+        input,
+    )
 }
 
 #[aoc(day05, part1)]
@@ -60,6 +49,24 @@ fn part2(input: &AocType) -> usize {
     input.iter().map(|r| r.end() - r.start() + 1).sum()
 }
 
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = AocType;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(input_generator(input)?)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -87,4 +94,13 @@ mod tests {
     fn test_p2() {
         assert_eq!(part2(&input_generator(TEST_INPUT).unwrap()), 14);
     }
+
+    #[test]
+    fn test_parse_error() {
+        let input = TEST_INPUT.replace("16-20", "16-2o");
+        let e = input_generator(&input).unwrap_err();
+        assert_eq!((e.line, e.column), (3, 5));
+        assert_eq!(e.excerpt, "16-2o");
+        assert_eq!(e.expected, "'\\n'");
+    }
 }
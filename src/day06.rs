@@ -1,48 +1,58 @@
-use std::collections::HashMap;
+use std::error::Error;
 
 use nom::{
-    branch::{alt, permutation},
+    branch::alt,
     character::complete::{self, newline, space0, space1},
-    combinator::{all_consuming, opt, value},
+    combinator::value,
+    error::context,
     multi::separated_list1,
     sequence::{delimited, separated_pair, terminated},
-    IResult, Parser,
+    Parser,
 };
 
-use crate::convert_iresult_to_owned;
+use crate::{
+    grid::Grid,
+    parse::{ParseError, Span},
+    parse_all,
+    solution::{Answer, Solution},
+    IResult,
+};
 
 type AocType = (Vec<usize>, Operator); // factor, value
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-enum Operator {
+pub enum Operator {
     Product,
     Sum,
 }
 
-fn parse_operator(input: &str) -> IResult<&str, Operator> {
-    alt((
-        value(Operator::Sum, complete::char('+')),
-        value(Operator::Product, complete::char('*')),
-    ))
+fn parse_operator(input: Span) -> IResult<Span, Operator> {
+    context(
+        "operator",
+        alt((
+            value(Operator::Sum, complete::char('+')),
+            value(Operator::Product, complete::char('*')),
+        )),
+    )
     .parse(input)
 }
 
 #[aoc_generator(day06, part1)]
-fn input_generator_p1(input: &str) -> Result<Vec<AocType>, nom::Err<nom::error::Error<String>>> {
-    let intermidiate = all_consuming(terminated(
-        separated_pair(
-            separated_list1(
+fn input_generator_p1(input: &str) -> Result<Vec<AocType>, ParseError> {
+    let (numbers, operators) = parse_all(
+        terminated(
+            separated_pair(
+                separated_list1(
+                    newline,
+                    delimited(space0, separated_list1(space1, complete::usize), space0),
+                ),
                 newline,
-                delimited(space0, separated_list1(space1, complete::usize), space0),
+                separated_list1(space1, parse_operator),
             ),
-            newline,
-            separated_list1(space1, parse_operator),
+            space0,
         ),
-        permutation((opt(space1), opt(newline))),
-    ))
-    .parse(input);
-
-    let (numbers, operators) = convert_iresult_to_owned(intermidiate)?;
+        input,
+    )?;
 
     Ok(operators
         .into_iter()
@@ -52,46 +62,41 @@ fn input_generator_p1(input: &str) -> Result<Vec<AocType>, nom::Err<nom::error::
 }
 
 #[aoc_generator(day06, part2)]
-fn input_generator_p2(input: &str) -> Result<Vec<AocType>, nom::Err<nom::error::Error<String>>> {
-    let mut lines: Vec<_> = input.lines().collect();
-    let last_row = lines.pop().unwrap();
-
-    let nr_of_number_lines = lines.len();
-    let max_columns = last_row.len();
-
-    let pos_values: HashMap<_, _> = lines
-        .into_iter()
-        .enumerate()
-        .flat_map(|(ln, l)| {
-            l.chars()
-                .enumerate()
-                .filter_map(move |(col, c)| Some(((col, ln), c.to_digit(10)? as usize)))
-        })
+fn input_generator_p2(input: &str) -> Result<Vec<AocType>, ParseError> {
+    // trailing spaces are easily lost, so short rows are padded back to the widest one
+    let width = input.lines().map(str::len).max().unwrap_or(0);
+    let padded: String = input
+        .lines()
+        .map(|line| format!("{line:width$}\n"))
         .collect();
+    let grid = Grid::parse(&padded, Some)?;
+    let operator_row = grid.height().saturating_sub(1);
 
-    let mut iter = last_row
-        .chars()
-        .enumerate()
-        .filter(|(_, c)| *c != ' ')
+    let mut iter = grid
+        .iter()
+        .filter(|&((_, y), &c)| y == operator_row && c != ' ')
         .peekable();
 
     let mut v: Vec<AocType> = Vec::new();
 
-    while let Some((pos, operator)) = iter.next() {
-        let col_max = iter.peek().map(|(c, _)| c - 1).unwrap_or(max_columns);
+    while let Some(((pos, _), operator)) = iter.next() {
+        let col_max = iter.peek().map(|((c, _), _)| c - 1).unwrap_or(grid.width());
         let mut numbers = Vec::new();
         for c in pos..col_max {
             let mut number = 0;
-            for row in 0..nr_of_number_lines {
-                if let Some(d) = pos_values.get(&(c, row)) {
+            for row in 0..operator_row {
+                if let Some(d) = grid[(c, row)].to_digit(10) {
                     number *= 10;
-                    number += d;
+                    number += d as usize;
                 }
             }
             assert_ne!(number, 0);
             numbers.push(number);
         }
-        let o = convert_iresult_to_owned(parse_operator(&format!("{operator}")))?;
+        let o = parse_all(parse_operator, &operator.to_string()).map_err(|e| {
+            let line = input.lines().nth(operator_row).unwrap_or("");
+            ParseError::new(operator_row as u32 + 1, pos + 1, line, &e.expected)
+        })?;
 
         v.push((numbers, o));
     }
@@ -118,6 +123,24 @@ fn part2(input: &[AocType]) -> usize {
     part1(input)
 }
 
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = (Vec<AocType>, Vec<AocType>);
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok((input_generator_p1(input)?, input_generator_p2(input)?))
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        part1(&input.0).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        part2(&input.1).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -138,4 +161,16 @@ mod tests {
     fn test_p2() {
         assert_eq!(part1(&input_generator_p2(TEST_INPUT).unwrap()), 3263827)
     }
+
+    #[test]
+    fn test_ragged_rows() {
+        let ragged: String = TEST_INPUT
+            .lines()
+            .map(|line| line.trim_end().to_owned() + "\n")
+            .collect();
+        assert_eq!(
+            input_generator_p2(&ragged).unwrap(),
+            input_generator_p2(TEST_INPUT).unwrap()
+        );
+    }
 }
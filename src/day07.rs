@@ -1,60 +1,48 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+};
+
+use crate::{
+    grid::Grid,
+    parse::ParseError,
+    solution::{Answer, Solution},
+};
 
 #[derive(Debug)]
-struct AocType {
-    splitters: HashSet<(usize, usize)>,
+pub struct AocType {
+    splitters: Grid<bool>,
     start: (usize, usize),
-    size: (usize, usize),
 }
 
-#[aoc_generator(day07)]
-fn input_generator(input: &str) -> AocType {
-    let size = {
-        let mut lines = input.lines();
-        let height = lines.clone().count();
-        let width = lines.next().map(|line| line.len()).unwrap();
-        (width, height)
-    };
-    let mut lines = input.lines().enumerate();
-    let start = lines
-        .next()
-        .map(|(_, line)| line)
-        .unwrap()
-        .char_indices()
-        .find(|&(_, c)| c == 'S')
-        .map(|(i, _)| (i, 0))
-        .unwrap();
-
-    let splitters = lines
-        .flat_map(|(line_num, line)| {
-            line.char_indices().filter_map(
-                move |(i, c)| {
-                    if c == '^' {
-                        Some((i, line_num))
-                    } else {
-                        None
-                    }
-                },
-            )
-        })
-        .collect();
-
-    AocType {
-        splitters,
-        start,
-        size,
+impl AocType {
+    fn is_splitter(&self, pos: (usize, usize)) -> bool {
+        self.splitters.get(pos) == Some(&true)
     }
 }
 
+#[aoc_generator(day07)]
+fn input_generator(input: &str) -> Result<AocType, ParseError> {
+    let (splitters, markers) = Grid::parse_marked(input, &['S'], |c| match c {
+        '^' => Some(true),
+        '.' | 'S' => Some(false),
+        _ => None,
+    })?;
+    let start = markers
+        .single('S')
+        .ok_or_else(|| ParseError::new(1, 1, input.lines().next().unwrap_or(""), "a single 'S'"))?;
+    Ok(AocType { splitters, start })
+}
+
 #[aoc(day07, part1)]
 fn part1(input: &AocType) -> usize {
     let mut splits = 0;
     let mut beams = HashSet::new();
     beams.insert(input.start.0);
-    for y in 1..input.size.1 {
+    for y in 1..input.splitters.height() {
         let mut new_beams = HashSet::new();
         for &beam in &beams {
-            if input.splitters.contains(&(beam, y)) {
+            if input.is_splitter((beam, y)) {
                 splits += 1;
                 new_beams.insert(beam - 1);
                 new_beams.insert(beam + 1);
@@ -72,10 +60,10 @@ fn part1(input: &AocType) -> usize {
 fn part2(input: &AocType) -> usize {
     let mut beams = HashMap::new();
     beams.insert(input.start.0, 1);
-    for y in 1..input.size.1 {
+    for y in 1..input.splitters.height() {
         let mut new_beams = HashMap::new();
         for (beam, amount) in &beams {
-            if input.splitters.contains(&(*beam, y)) {
+            if input.is_splitter((*beam, y)) {
                 new_beams
                     .entry(beam + 1)
                     .and_modify(|e| *e += amount)
@@ -97,6 +85,24 @@ fn part2(input: &AocType) -> usize {
     beams.values().sum()
 }
 
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = AocType;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(input_generator(input)?)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -120,13 +126,21 @@ mod tests {
         ...............
     "};
 
+    #[test]
+    fn test_parse_error() {
+        let e = input_generator(&TEST_INPUT.replace('S', ".")).unwrap_err();
+        assert_eq!(e.expected, "a single 'S'");
+        let e = input_generator(&TEST_INPUT.replacen("^.", "^", 1)).unwrap_err();
+        assert_eq!((e.line, e.column), (3, 15));
+    }
+
     #[test]
     fn test_p1() {
-        assert_eq!(part1(&input_generator(TEST_INPUT)), 21);
+        assert_eq!(part1(&input_generator(TEST_INPUT).unwrap()), 21);
     }
 
     #[test]
     fn test_p2() {
-        assert_eq!(part2(&input_generator(TEST_INPUT)), 40);
+        assert_eq!(part2(&input_generator(TEST_INPUT).unwrap()), 40);
     }
 }
@@ -1,34 +1,26 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, error::Error};
 
 use itertools::Itertools;
-use nom::{
-    character::complete::{self, newline},
-    combinator::{all_consuming, opt},
-    multi::separated_list1,
-    sequence::terminated,
-    Parser,
-};
+use nom::{error::context, Parser};
 
-use crate::convert_iresult_to_owned;
+use crate::{
+    parse::ParseError,
+    parse_all,
+    parsers::{coordinate, lines, number},
+    solution::{Answer, Solution},
+};
 
 type AocType = (usize, usize, usize); // factor, value
 
 #[aoc_generator(day08)]
-pub fn input_generator(input: &str) -> Result<Vec<AocType>, nom::Err<nom::error::Error<String>>> {
-    let ret = all_consuming(terminated(
-        separated_list1(
-            newline,
-            (
-                terminated(complete::usize, complete::char(',')),
-                terminated(complete::usize, complete::char(',')),
-                complete::usize,
-            ),
-        ),
-        opt(newline),
-    ))
-    .parse(input);
-
-    convert_iresult_to_owned(ret)
+pub fn input_generator(input: &str) -> Result<Vec<AocType>, ParseError> {
+    parse_all(
+        lines(context(
+            "junction box",
+            coordinate(number).map(|[x, y, z]| (x, y, z)),
+        )),
+        input,
+    )
 }
 
 fn squared_distance(a: &AocType, b: &AocType) -> usize {
@@ -109,6 +101,24 @@ fn part2(input: &[AocType]) -> usize {
     connect_circuts(input, usize::MAX)
 }
 
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = Vec<AocType>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(input_generator(input)?)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,29 +1,21 @@
+use std::error::Error;
+
 use itertools::Itertools;
-use nom::{
-    character::complete::{self, newline},
-    combinator::{all_consuming, opt},
-    multi::separated_list1,
-    sequence::{separated_pair, terminated},
-    Parser,
-};
+use nom::Parser;
 
-use crate::convert_iresult_to_owned;
+use crate::{
+    parse::ParseError,
+    parse_all,
+    parsers::{coordinate, lines, number},
+    solution::{Answer, Solution},
+};
 
 type AocType = (usize, usize); // factor, value
 type Edge = (AocType, AocType);
 
 #[aoc_generator(day09)]
-pub fn input_generator(input: &str) -> Result<Vec<AocType>, nom::Err<nom::error::Error<String>>> {
-    let ret = all_consuming(terminated(
-        separated_list1(
-            newline,
-            separated_pair(complete::usize, complete::char(','), complete::usize),
-        ),
-        opt(newline),
-    ))
-    .parse(input);
-
-    convert_iresult_to_owned(ret)
+pub fn input_generator(input: &str) -> Result<Vec<AocType>, ParseError> {
+    parse_all(lines(coordinate(number).map(|[x, y]| (x, y))), input)
 }
 
 fn squared_area(a: &AocType, b: &AocType) -> usize {
@@ -82,6 +74,24 @@ fn part2(red_tiles: &[AocType]) -> usize {
     result
 }
 
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = Vec<AocType>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(input_generator(input)?)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,27 +1,32 @@
+use std::error::Error;
+
 use good_lp::{microlp, variable, variables, Expression, Solution, SolverModel};
 use itertools::Itertools;
 use nom::{
     branch::alt,
     character::complete::{self, space1},
-    multi::{many1, separated_list1},
+    error::context,
+    multi::many1,
     sequence::delimited,
-    IResult, Parser,
+    Parser,
 };
 
-use crate::convert_iresult_to_owned;
+use crate::{
+    parse::{ParseError, Span},
+    parse_all,
+    parsers::{comma_list, lines, number, space_list},
+    solution::{self, Answer},
+    IResult,
+};
 
 #[derive(Debug, Clone)]
-struct AocType {
+pub struct AocType {
     goal: Vec<bool>,
     buttons: Vec<Vec<usize>>,
     joltage: Vec<usize>,
 }
 
-fn parse_comma_separated_usize(input: &str) -> IResult<&str, Vec<usize>> {
-    separated_list1(complete::char(','), complete::usize).parse(input)
-}
-
-fn light_goal_parser(input: &str) -> IResult<&str, Vec<bool>> {
+fn light_goal_parser(input: Span) -> IResult<Span, Vec<bool>> {
     delimited(
         complete::char('['),
         many1(alt((
@@ -34,38 +39,36 @@ fn light_goal_parser(input: &str) -> IResult<&str, Vec<bool>> {
 }
 
 #[aoc_generator(day10)]
-fn input_generator(input: &str) -> Result<Vec<AocType>, nom::Err<nom::error::Error<String>>> {
-    let ret = separated_list1(
-        complete::newline,
-        (
-            light_goal_parser,
-            delimited(
-                space1,
-                separated_list1(
+fn input_generator(input: &str) -> Result<Vec<AocType>, ParseError> {
+    parse_all(
+        lines(
+            (
+                context("light diagram", light_goal_parser),
+                delimited(
                     space1,
-                    delimited(
-                        complete::char('('),
-                        parse_comma_separated_usize,
-                        complete::char(')'),
+                    context(
+                        "button list",
+                        space_list(delimited(
+                            complete::char('('),
+                            comma_list(number),
+                            complete::char(')'),
+                        )),
                     ),
+                    space1,
+                ),
+                context(
+                    "joltage requirements",
+                    delimited(complete::char('{'), comma_list(number), complete::char('}')),
                 ),
-                space1,
-            ),
-            delimited(
-                complete::char('{'),
-                parse_comma_separated_usize,
-                complete::char('}'),
-            ),
-        )
-            .map(|(lights, buttons, joltage)| AocType {
-                goal: lights,
-                buttons,
-                joltage,
-            }),
+            )
+                .map(|(lights, buttons, joltage)| AocType {
+                    goal: lights,
+                    buttons,
+                    joltage,
+                }),
+        ),
+        input,
     )
-    .parse(input);
-
-    convert_iresult_to_owned(ret)
 }
 
 fn min_presses_to_goal(aoc: &AocType) -> usize {
@@ -174,6 +177,24 @@ fn part2(input: &[AocType]) -> usize {
     input.iter().map(min_presses_to_joltage).sum()
 }
 
+pub struct Day10;
+
+impl solution::Solution for Day10 {
+    type Input = Vec<AocType>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(input_generator(input)?)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -190,6 +211,22 @@ mod tests {
         assert_eq!(p.len(), 3);
     }
 
+    #[test]
+    fn test_parse_error() {
+        let input = TEST_INPUT.replace("(1,3)", "(1;3)");
+        let e = input_generator(&input).unwrap_err();
+        assert_eq!((e.line, e.column), (1, 12));
+        assert_eq!(e.expected, "'{'");
+        assert_eq!(e.context, ["joltage requirements"]);
+    }
+
+    #[test]
+    fn test_stray_content() {
+        let e = input_generator(&format!("{TEST_INPUT}[..#]")).unwrap_err();
+        assert_eq!((e.line, e.column), (3, 65));
+        assert_eq!(e.expected, r#"the end of the input, found "\n[..#]""#);
+    }
+
     #[test]
     fn test_p1() {
         assert_eq!(part1(&input_generator(TEST_INPUT).unwrap()), 7);
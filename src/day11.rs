@@ -1,96 +1,126 @@
-use std::collections::HashMap;
-
-use nom::{
-    bytes::complete::tag,
-    character::complete::{alpha1, newline, space1},
-    combinator::{all_consuming, opt},
-    multi::separated_list1,
-    sequence::{separated_pair, terminated},
-    IResult, Parser,
-};
+use std::{collections::HashMap, error::Error};
+
 use petgraph::prelude::DiGraphMap;
 
-type Graph<'a> = DiGraphMap<&'a str, ()>;
+use crate::{
+    parse::ParseError,
+    parse_all,
+    parsers::{labelled, lines, word},
+    solution::{Answer, Solution},
+};
+
+/// The device graph, with every device name mapped to a node id so the graph can own its
+/// nodes instead of borrowing names from the input.
+#[derive(Debug, Clone)]
+pub struct Devices {
+    graph: DiGraphMap<usize, ()>,
+    ids: HashMap<String, usize>,
+}
+
+impl Devices {
+    fn id(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    fn intern(&mut self, name: &str) -> usize {
+        let next = self.ids.len();
+        let id = *self.ids.entry(name.to_owned()).or_insert(next);
+        self.graph.add_node(id);
+        id
+    }
+}
 
-fn parse_tree<'a>(input: &'a str) -> IResult<&'a str, Graph<'a>> {
-    let mut graph = DiGraphMap::new();
+#[aoc_generator(day11)]
+fn parse_tree(input: &str) -> Result<Devices, ParseError> {
+    let mut devices = Devices {
+        graph: DiGraphMap::new(),
+        ids: HashMap::new(),
+    };
 
-    let values = all_consuming(terminated(
-        separated_list1(
-            newline,
-            separated_pair(alpha1, tag(": "), separated_list1(space1, alpha1)),
-        ),
-        opt(newline),
-    ))
-    .parse(input)?;
+    let values = parse_all(lines(labelled(word, word)), input)?;
 
-    for (parent, children) in values.1 {
-        graph.add_node(parent);
+    for (parent, children) in values {
+        let parent = devices.intern(parent);
         for child in children {
-            graph.add_node(child);
-            graph.add_edge(parent, child, ());
+            let child = devices.intern(child);
+            devices.graph.add_edge(parent, child, ());
         }
     }
 
-    Ok((input, graph))
+    Ok(devices)
 }
 
-fn traverse_p1<'a, 'b>(node: &'a str, graph: &Graph<'b>) -> usize
-where
-    'a: 'b,
-{
-    if node == "out" {
+fn traverse_p1(node: usize, out: Option<usize>, devices: &Devices) -> usize {
+    if Some(node) == out {
         return 1;
     }
-    graph
+    devices
+        .graph
         .neighbors(node)
-        .map(|n| traverse_p1(n, graph))
+        .map(|n| traverse_p1(n, out, devices))
         .sum::<usize>()
 }
 
 #[aoc(day11, part1)]
-fn part1(input: &str) -> usize {
-    let (_, graph) = parse_tree(input).unwrap();
-
-    traverse_p1("you", &graph)
+fn part1(devices: &Devices) -> usize {
+    match devices.id("you") {
+        Some(you) => traverse_p1(you, devices.id("out"), devices),
+        None => 0,
+    }
 }
 
-fn traverse_p2<'a, 'b, 'c>(
-    node: &'a str,
+fn traverse_p2(
+    node: usize,
     mut dac: bool,
     mut fft: bool,
-    graph: &Graph<'b>,
-    dp: &mut HashMap<(&'c str, bool, bool), usize>,
-) -> usize
-where
-    'a: 'b,
-    'b: 'c,
-{
+    devices: &Devices,
+    dp: &mut HashMap<(usize, bool, bool), usize>,
+) -> usize {
     if let Some(&res) = dp.get(&(node, dac, fft)) {
         return res;
     }
-    if node == "out" {
+    if Some(node) == devices.id("out") {
         return if dac && fft { 1 } else { 0 };
-    } else if node == "dac" {
+    } else if Some(node) == devices.id("dac") {
         dac = true;
-    } else if node == "fft" {
+    } else if Some(node) == devices.id("fft") {
         fft = true;
     }
-    let ans = graph
+    let ans = devices
+        .graph
         .neighbors(node)
-        .map(|n| traverse_p2(n, dac, fft, graph, dp))
+        .map(|n| traverse_p2(n, dac, fft, devices, dp))
         .sum::<usize>();
     dp.insert((node, dac, fft), ans);
     ans
 }
 
 #[aoc(day11, part2)]
-fn part2(input: &str) -> usize {
-    let (_, graph) = parse_tree(input).unwrap();
-
+fn part2(devices: &Devices) -> usize {
     let mut dp = HashMap::new();
 
-    traverse_p2("svr", false, false, &graph, &mut dp)
+    match devices.id("svr") {
+        Some(svr) => traverse_p2(svr, false, false, devices, &mut dp),
+        None => 0,
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Devices;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(parse_tree(input)?)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
 }
 
 #[cfg(test)]
@@ -128,17 +158,28 @@ mod tests {
 
     #[test]
     fn test_parse() {
-        let (_, graph) = parse_tree(TEST_INPUT1).unwrap();
-        assert_eq!(graph.node_count(), 11);
+        let devices = parse_tree(TEST_INPUT1).unwrap();
+        assert_eq!(devices.graph.node_count(), 11);
+        assert_eq!(devices.graph.edge_count(), 17);
+    }
+
+    #[test]
+    fn test_stray_content() {
+        let e = parse_tree(&format!("{TEST_INPUT1}\n")).unwrap_err();
+        assert_eq!((e.line, e.column), (10, 9));
+
+        let e = parse_tree(&TEST_INPUT1.replace("ggg: out", "ggg: out!")).unwrap_err();
+        assert_eq!((e.line, e.column), (8, 9));
+        assert!(e.expected.ends_with(r#"found "!\nhhh: ccc fff iii\ni"..."#));
     }
 
     #[test]
     fn test_p1() {
-        assert_eq!(part1(TEST_INPUT1), 5);
+        assert_eq!(part1(&parse_tree(TEST_INPUT1).unwrap()), 5);
     }
 
     #[test]
     fn test_p2() {
-        assert_eq!(part2(TEST_INPUT2), 2);
+        assert_eq!(part2(&parse_tree(TEST_INPUT2).unwrap()), 2);
     }
 }
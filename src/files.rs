@@ -0,0 +1,30 @@
+use std::{io, path::Path};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Reads the TOML table at `path`, starting an empty one if the file does not exist yet.
+pub fn load_toml<T: DeserializeOwned + Default>(path: &Path) -> io::Result<T> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => {
+            toml::from_str(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(T::default()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Writes `value` to `path` as TOML, creating the directory it goes in.
+pub fn save_toml<T: Serialize>(path: &Path, value: &T) -> io::Result<()> {
+    let content =
+        toml::to_string(value).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, content)
+}
+
+/// A path in the system temp directory for `name`, unique to the running tests.
+#[cfg(test)]
+pub fn temp_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("aoc-{}-{}", std::process::id(), name))
+}
@@ -1,17 +1,22 @@
 use std::{
+    collections::BTreeMap,
     fmt::{Debug, Display},
     ops::{Index, IndexMut, Not},
+    str::FromStr,
 };
 
 use itertools::Itertools;
 
-pub struct Grid<const N: usize, T> {
+use crate::{parse::ParseError, point::Point};
+
+/// A square grid with its size fixed at compile time.
+pub struct SquareGrid<const N: usize, T> {
     grid: [[T; N]; N],
 }
 
-impl<const N: usize, T> Grid<N, T> {
-    pub fn iter(&self) -> GridIter<'_, N, T> {
-        GridIter {
+impl<const N: usize, T> SquareGrid<N, T> {
+    pub fn iter(&self) -> SquareGridIter<'_, N, T> {
+        SquareGridIter {
             grid: self,
             pos: (0, 0),
         }
@@ -22,7 +27,7 @@ impl<const N: usize, T> Grid<N, T> {
     }
 }
 
-impl<const N: usize, T> Display for Grid<N, T>
+impl<const N: usize, T> Display for SquareGrid<N, T>
 where
     T: Display,
 {
@@ -36,7 +41,7 @@ where
     }
 }
 
-impl<const N: usize, T> Debug for Grid<N, T>
+impl<const N: usize, T> Debug for SquareGrid<N, T>
 where
     T: Display,
 {
@@ -45,7 +50,7 @@ where
     }
 }
 
-impl<const N: usize, T> Index<(usize, usize)> for Grid<N, T> {
+impl<const N: usize, T> Index<(usize, usize)> for SquareGrid<N, T> {
     type Output = T;
 
     fn index(&self, index: (usize, usize)) -> &Self::Output {
@@ -53,13 +58,13 @@ impl<const N: usize, T> Index<(usize, usize)> for Grid<N, T> {
     }
 }
 
-impl<const N: usize, T> IndexMut<(usize, usize)> for Grid<N, T> {
+impl<const N: usize, T> IndexMut<(usize, usize)> for SquareGrid<N, T> {
     fn index_mut(&mut self, index: (usize, usize)) -> &mut Self::Output {
         self.grid.index_mut(index.1).index_mut(index.0)
     }
 }
 
-impl<const N: usize, T> Default for Grid<N, T>
+impl<const N: usize, T> Default for SquareGrid<N, T>
 where
     T: Default + Copy,
 {
@@ -70,12 +75,12 @@ where
     }
 }
 
-pub struct GridIter<'a, const N: usize, T> {
-    grid: &'a Grid<N, T>,
+pub struct SquareGridIter<'a, const N: usize, T> {
+    grid: &'a SquareGrid<N, T>,
     pos: (usize, usize),
 }
 
-impl<'a, const N: usize, T> Iterator for GridIter<'a, N, T> {
+impl<'a, const N: usize, T> Iterator for SquareGridIter<'a, N, T> {
     type Item = ((usize, usize), &'a T);
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -89,6 +94,257 @@ impl<'a, const N: usize, T> Iterator for GridIter<'a, N, T> {
     }
 }
 
+/// A rectangular grid sized at runtime, stored row by row and indexed by `(x, y)`.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    tiles: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid of `width` by `height` tiles, all `tile`.
+    pub fn new(width: usize, height: usize, tile: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            tiles: vec![tile; width * height],
+        }
+    }
+
+    /// A grid from rows of tiles. Returns `None` if the rows differ in length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+        Some(Self {
+            width,
+            height,
+            tiles: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Parses one tile per character, line by line. `tile` returns `None` for characters
+    /// that are not a tile.
+    pub fn parse(input: &str, tile: impl FnMut(char) -> Option<T>) -> Result<Self, ParseError> {
+        Ok(Self::parse_marked(input, &[], tile)?.0)
+    }
+
+    /// Like [`Grid::parse`], also returning where each of the `markers` characters is.
+    pub fn parse_marked(
+        input: &str,
+        markers: &[char],
+        mut tile: impl FnMut(char) -> Option<T>,
+    ) -> Result<(Self, Markers), ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut tiles = Vec::new();
+        let mut found = Markers::default();
+
+        for (y, line) in input.lines().enumerate() {
+            let line_nr = y as u32 + 1;
+            let mut x = 0;
+            for c in line.chars() {
+                if markers.contains(&c) {
+                    found.0.entry(c).or_default().push((x, y));
+                }
+                match tile(c) {
+                    Some(t) => tiles.push(t),
+                    None => {
+                        return Err(ParseError::new(
+                            line_nr,
+                            x + 1,
+                            line,
+                            &format!("a tile, not {c:?}"),
+                        ))
+                    }
+                }
+                x += 1;
+            }
+            match width {
+                None => width = Some(x),
+                Some(width) if width != x => {
+                    return Err(ParseError::new(
+                        line_nr,
+                        width.min(x) + 1,
+                        line,
+                        &format!("a line of {width} tiles"),
+                    ))
+                }
+                Some(_) => (),
+            }
+            height += 1;
+        }
+
+        let grid = Self {
+            width: width.unwrap_or(0),
+            height,
+            tiles,
+        };
+        Ok((grid, found))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    pub fn contains(&self, pos: (usize, usize)) -> bool {
+        pos.0 < self.width && pos.1 < self.height
+    }
+
+    pub fn get(&self, pos: (usize, usize)) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.tiles[pos.1 * self.width + pos.0])
+    }
+
+    pub fn get_mut(&mut self, pos: (usize, usize)) -> Option<&mut T> {
+        self.contains(pos)
+            .then(|| &mut self.tiles[pos.1 * self.width + pos.0])
+    }
+
+    pub fn iter(&self) -> GridIter<'_, T> {
+        GridIter {
+            width: self.width,
+            tiles: self.tiles.iter().enumerate(),
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on 0, an empty grid has no tiles either way
+        self.tiles.chunks(self.width.max(1))
+    }
+}
+
+impl<T> Display for Grid<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let grid = self
+            .rows()
+            .map(|line| line.iter().map(|tile| tile.to_string()).collect::<String>())
+            .join("\n");
+        writeln!(f, "\n{}", grid)
+    }
+}
+
+impl<T> Debug for Grid<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, index: (usize, usize)) -> &Self::Output {
+        assert!(
+            self.contains(index),
+            "{:?} is outside a {}x{} grid",
+            index,
+            self.width,
+            self.height
+        );
+        &self.tiles[index.1 * self.width + index.0]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, index: (usize, usize)) -> &mut Self::Output {
+        assert!(
+            self.contains(index),
+            "{:?} is outside a {}x{} grid",
+            index,
+            self.width,
+            self.height
+        );
+        &mut self.tiles[index.1 * self.width + index.0]
+    }
+}
+
+impl<const N: usize, T> From<SquareGrid<N, T>> for Grid<T> {
+    fn from(grid: SquareGrid<N, T>) -> Self {
+        Self {
+            width: N,
+            height: N,
+            tiles: grid.grid.into_iter().flatten().collect(),
+        }
+    }
+}
+
+impl<const N: usize, T> From<&SquareGrid<N, T>> for Grid<T>
+where
+    T: Clone,
+{
+    fn from(grid: &SquareGrid<N, T>) -> Self {
+        Self {
+            width: N,
+            height: N,
+            tiles: grid.grid.iter().flatten().cloned().collect(),
+        }
+    }
+}
+
+/// A grid of tiles that can be read from a single character, like `#` or `.`.
+impl<T> FromStr for Grid<T>
+where
+    T: TryFrom<char>,
+{
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, |c| T::try_from(c).ok())
+    }
+}
+
+/// Positions of marker characters found by [`Grid::parse_marked`], in reading order.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Markers(BTreeMap<char, Vec<(usize, usize)>>);
+
+impl Markers {
+    pub fn positions(&self, marker: char) -> &[(usize, usize)] {
+        self.0.get(&marker).map_or(&[], Vec::as_slice)
+    }
+
+    /// The position of `marker` if it occurs exactly once.
+    pub fn single(&self, marker: char) -> Option<(usize, usize)> {
+        match self.positions(marker) {
+            &[pos] => Some(pos),
+            _ => None,
+        }
+    }
+}
+
+pub struct GridIter<'a, T> {
+    width: usize,
+    tiles: std::iter::Enumerate<std::slice::Iter<'a, T>>,
+}
+
+impl<'a, T> Iterator for GridIter<'a, T> {
+    type Item = ((usize, usize), &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (i, tile) = self.tiles.next()?;
+        Some(((i % self.width, i / self.width), tile))
+    }
+}
+
 use Direction::*;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -127,61 +383,53 @@ impl Direction {
         [North, East, South, West]
     }
 
-    pub fn step(&self, pos: (usize, usize), bound: usize) -> Option<(usize, usize)> {
+    /// The offset of one step in this direction.
+    pub const fn offset(&self) -> Point {
         match self {
-            North => Some((pos.0, pos.1.checked_sub(1)?)),
-            South => {
-                let added = pos.1 + 1;
-                if added < bound {
-                    Some((pos.0, added))
-                } else {
-                    None
-                }
-            }
-            East => {
-                let added = pos.0 + 1;
-                if added < bound {
-                    Some((added, pos.1))
-                } else {
-                    None
-                }
-            }
-            West => Some((pos.0.checked_sub(1)?, pos.1)),
-            NorthEast => {
-                let added = pos.0 + 1;
-                if added < bound {
-                    Some((added, pos.1.checked_sub(1)?))
-                } else {
-                    None
-                }
-            }
-            SouthEast => {
-                let added = pos.0 + 1;
-                let added2 = pos.1 + 1;
-                if added < bound && added2 < bound {
-                    Some((added, added2))
-                } else {
-                    None
-                }
-            }
-            SouthWest => {
-                let added = pos.0.checked_sub(1)?;
-                let added2 = pos.1 + 1;
-                if added2 < bound {
-                    Some((added, added2))
-                } else {
-                    None
-                }
-            }
-            NorthWest => {
-                let added = pos.0.checked_sub(1)?;
-                if added < bound {
-                    Some((added, pos.1.checked_sub(1)?))
-                } else {
-                    None
-                }
-            }
+            North => Point::new(0, -1),
+            NorthEast => Point::new(1, -1),
+            East => Point::new(1, 0),
+            SouthEast => Point::new(1, 1),
+            South => Point::new(0, 1),
+            SouthWest => Point::new(-1, 1),
+            West => Point::new(-1, 0),
+            NorthWest => Point::new(-1, -1),
+        }
+    }
+
+    /// Steps from `pos` within a grid of `(width, height)`, `None` when leaving it.
+    pub fn step(&self, pos: (usize, usize), size: (usize, usize)) -> Option<(usize, usize)> {
+        (Point::from(pos) + *self).to_index(size)
+    }
+
+    /// Steps from `pos` within `grid`, `None` when leaving it.
+    pub fn step_in<T>(&self, pos: (usize, usize), grid: &Grid<T>) -> Option<(usize, usize)> {
+        self.step(pos, grid.size())
+    }
+
+    /// Steps from `pos` on a grid of `(width, height)` whose edges wrap around to the
+    /// opposite side, `None` when the grid is empty.
+    pub fn step_wrapping(
+        &self,
+        pos: (usize, usize),
+        size: (usize, usize),
+    ) -> Option<(usize, usize)> {
+        let (width, height) = size;
+        if width == 0 || height == 0 {
+            return None;
         }
+        let offset = self.offset();
+        let wrap = |value: usize, delta: i64, bound: usize| match delta {
+            -1 => (value + bound - 1) % bound,
+            1 => (value + 1) % bound,
+            _ => value,
+        };
+        Some((wrap(pos.0, offset.x, width), wrap(pos.1, offset.y, height)))
+    }
+
+    /// Steps from `pos` on a plane without edges.
+    pub fn step_unbounded(&self, pos: Point) -> Point {
+        pos + *self
     }
 }
 
@@ -201,3 +449,203 @@ impl Not for Direction {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid() {
+        let mut grid = Grid::new(3, 2, '.');
+        grid[(2, 1)] = '#';
+        assert_eq!(grid.size(), (3, 2));
+        assert_eq!(grid.get((2, 1)), Some(&'#'));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, 2)), None);
+        assert_eq!(grid.to_string(), "\n...\n..#\n");
+        assert_eq!(
+            grid.iter().filter(|(_, &c)| c == '#').collect::<Vec<_>>(),
+            [((2, 1), &'#')]
+        );
+        assert_eq!(grid.iter().last(), Some(((2, 1), &'#')));
+    }
+
+    #[test]
+    fn test_from_rows() {
+        let grid = Grid::from_rows(vec![vec![1, 2], vec![3, 4], vec![5, 6]]).unwrap();
+        assert_eq!((grid.width(), grid.height()), (2, 3));
+        assert_eq!(grid[(1, 2)], 6);
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [[1, 2], [3, 4], [5, 6]]);
+        assert_eq!(Grid::from_rows(vec![vec![1, 2], vec![3]]), None);
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Tile {
+        Open,
+        Wall,
+    }
+
+    impl TryFrom<char> for Tile {
+        type Error = char;
+
+        fn try_from(c: char) -> Result<Self, Self::Error> {
+            match c {
+                '.' => Ok(Tile::Open),
+                '#' => Ok(Tile::Wall),
+                c => Err(c),
+            }
+        }
+    }
+
+    impl Display for Tile {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Tile::Open => write!(f, "."),
+                Tile::Wall => write!(f, "#"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid: Grid<Tile> = "#..\n.#.\n".parse().unwrap();
+        assert_eq!(grid.size(), (3, 2));
+        assert_eq!(grid[(1, 1)], Tile::Wall);
+        assert_eq!(grid[(2, 1)], Tile::Open);
+        assert_eq!(grid.to_string(), "\n#..\n.#.\n");
+
+        let e = "#..\n.x.".parse::<Grid<Tile>>().unwrap_err();
+        assert_eq!((e.line, e.column), (2, 2));
+        assert_eq!(e.expected, "a tile, not 'x'");
+
+        let e = "#..\n.#\n...".parse::<Grid<Tile>>().unwrap_err();
+        assert_eq!((e.line, e.column), (2, 3));
+        assert_eq!(e.expected, "a line of 3 tiles");
+        let e = "#..\n.#..".parse::<Grid<Tile>>().unwrap_err();
+        assert_eq!((e.line, e.column), (2, 4));
+
+        assert_eq!("".parse::<Grid<Tile>>().unwrap().size(), (0, 0));
+    }
+
+    #[test]
+    fn test_parse_marked() {
+        let (grid, markers) =
+            Grid::parse_marked("..S.\n^..^\n", &['S', '^', 'E'], |c| Some(c == '^')).unwrap();
+        assert_eq!(grid.size(), (4, 2));
+        assert_eq!(markers.single('S'), Some((2, 0)));
+        assert_eq!(markers.positions('^'), [(0, 1), (3, 1)]);
+        assert_eq!(markers.single('^'), None);
+        assert_eq!(markers.positions('E'), []);
+    }
+
+    /// Offsets written out independently of [`Direction::offset`].
+    const OFFSETS: [(Direction, i64, i64); 8] = [
+        (North, 0, -1),
+        (NorthEast, 1, -1),
+        (East, 1, 0),
+        (SouthEast, 1, 1),
+        (South, 0, 1),
+        (SouthWest, -1, 1),
+        (West, -1, 0),
+        (NorthWest, -1, -1),
+    ];
+
+    /// Every position of a 4 by 3 grid, covering all edges, corners and the inside.
+    fn positions() -> impl Iterator<Item = (usize, usize)> {
+        (0..3).flat_map(|y| (0..4).map(move |x| (x, y)))
+    }
+
+    #[test]
+    fn test_step_everywhere() {
+        let size = (4, 3);
+        for pos in positions() {
+            for (direction, dx, dy) in OFFSETS {
+                let x = pos.0 as i64 + dx;
+                let y = pos.1 as i64 + dy;
+                let inside = (0..4).contains(&x) && (0..3).contains(&y);
+                let expected = inside.then_some((x as usize, y as usize));
+                assert_eq!(
+                    direction.step(pos, size),
+                    expected,
+                    "{direction:?} from {pos:?}"
+                );
+
+                let wrapped = (x.rem_euclid(4) as usize, y.rem_euclid(3) as usize);
+                assert_eq!(
+                    direction.step_wrapping(pos, size),
+                    Some(wrapped),
+                    "{direction:?} wrapping from {pos:?}"
+                );
+                assert_eq!((!direction).step_wrapping(wrapped, size), Some(pos));
+
+                assert_eq!(direction.step_unbounded(Point::from(pos)), Point::new(x, y));
+            }
+        }
+    }
+
+    #[test]
+    fn test_step_corners() {
+        let size = (4, 3);
+        let corners = [
+            ((0, 0), [East, SouthEast, South]),
+            ((3, 0), [South, SouthWest, West]),
+            ((3, 2), [North, West, NorthWest]),
+            ((0, 2), [North, NorthEast, East]),
+        ];
+        for (corner, inward) in corners {
+            let allowed: Vec<_> = Direction::all()
+                .into_iter()
+                .filter(|direction| direction.step(corner, size).is_some())
+                .collect();
+            assert_eq!(allowed, inward, "from {corner:?}");
+        }
+
+        // the far edges are bounded by width and height separately
+        assert_eq!(East.step((2, 2), size), Some((3, 2)));
+        assert_eq!(South.step((3, 1), size), Some((3, 2)));
+        assert_eq!(SouthEast.step((2, 1), size), Some((3, 2)));
+        assert_eq!(NorthEast.step((3, 1), size), None);
+        assert_eq!(NorthWest.step((0, 1), size), None);
+        assert_eq!(SouthWest.step((1, 2), size), None);
+    }
+
+    #[test]
+    fn test_step_in_grid() {
+        let grid = Grid::new(4, 3, ());
+        for pos in positions() {
+            for direction in Direction::all() {
+                assert_eq!(direction.step_in(pos, &grid), direction.step(pos, (4, 3)));
+            }
+        }
+        assert_eq!(NorthWest.step_wrapping((0, 0), grid.size()), Some((3, 2)));
+    }
+
+    #[test]
+    fn test_step_empty() {
+        for size in [(0, 0), (0, 3), (4, 0)] {
+            for direction in Direction::all() {
+                assert_eq!(direction.step((0, 0), size), None);
+                assert_eq!(direction.step_wrapping((0, 0), size), None);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_outside() {
+        // row-major storage would otherwise wrap (2, 0) around to (0, 1)
+        let grid = Grid::new(2, 2, 0);
+        let _ = grid[(2, 0)];
+    }
+
+    #[test]
+    fn test_from_square_grid() {
+        let mut square = SquareGrid::<3, u8>::default();
+        square[(2, 0)] = 1;
+        square[(0, 2)] = 2;
+        let grid = Grid::from(&square);
+        assert_eq!(grid.size(), (3, 3));
+        assert_eq!(grid.to_string(), square.to_string());
+        assert_eq!(Grid::from(square), grid);
+    }
+}
@@ -0,0 +1,71 @@
+use std::path::{Path, PathBuf};
+
+use crate::client::{Client, ClientError};
+
+/// Puzzle inputs cached on disk as `<dir>/<year>/day<N>.txt`, the layout `cargo aoc` reads.
+pub struct InputStore {
+    dir: PathBuf,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Fetch {
+    Downloaded(PathBuf),
+    Cached(PathBuf),
+}
+
+impl InputStore {
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        Self {
+            dir: dir.as_ref().to_owned(),
+        }
+    }
+
+    pub fn path(&self, year: u32, day: u8) -> PathBuf {
+        self.dir
+            .join(year.to_string())
+            .join(format!("day{day}.txt"))
+    }
+
+    /// Downloads the input for the day, unless a copy already exists on disk.
+    pub fn fetch(&self, client: &Client, year: u32, day: u8) -> Result<Fetch, ClientError> {
+        let path = self.path(year, day);
+        if path.exists() {
+            return Ok(Fetch::Cached(path));
+        }
+
+        let input = client.input(year, day)?;
+        std::fs::create_dir_all(path.parent().unwrap())?;
+        std::fs::write(&path, input)?;
+        Ok(Fetch::Downloaded(path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{client::stand_in, files::temp_path};
+
+    #[test]
+    fn test_fetch_once() {
+        let dir = temp_path("input");
+        let store = InputStore::new(&dir);
+        let server = stand_in::serve(vec![(200, "L68\nL30\n")]);
+        let client = Client::new(&server.url, "abc123");
+
+        let path = store.path(2025, 1);
+        assert_eq!(path, dir.join("2025").join("day1.txt"));
+        assert_eq!(
+            store.fetch(&client, 2025, 1).unwrap(),
+            Fetch::Downloaded(path.clone())
+        );
+        // the stand-in only answers once, a second request would fail
+        assert_eq!(
+            store.fetch(&client, 2025, 1).unwrap(),
+            Fetch::Cached(path.clone())
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "L68\nL30\n");
+        assert_eq!(server.requests().len(), 1);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,350 @@
+use std::{
+    fmt::Display,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use aoc::solution::Answer;
+use jiff::Timestamp;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    RateLimited,
+    /// The server's reply could not be classified, so the answer may or may not count.
+    Unknown,
+}
+
+impl Outcome {
+    /// Classifies the page returned after posting an answer.
+    pub fn from_response(page: &str) -> Option<Outcome> {
+        if page.contains("That's the right answer") {
+            Some(Outcome::Correct)
+        } else if page.contains("You gave an answer too recently") {
+            Some(Outcome::RateLimited)
+        } else if page.contains("your answer is too high") {
+            Some(Outcome::TooHigh)
+        } else if page.contains("your answer is too low") {
+            Some(Outcome::TooLow)
+        } else if page.contains("That's not the right answer") {
+            Some(Outcome::Incorrect)
+        } else {
+            None
+        }
+    }
+
+    fn is_wrong(&self) -> bool {
+        matches!(
+            self,
+            Outcome::Incorrect | Outcome::TooHigh | Outcome::TooLow
+        )
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Outcome::Correct => "correct",
+            Outcome::Incorrect => "incorrect",
+            Outcome::TooHigh => "too-high",
+            Outcome::TooLow => "too-low",
+            Outcome::RateLimited => "rate-limited",
+            Outcome::Unknown => "unknown",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "correct" => Outcome::Correct,
+            "incorrect" => Outcome::Incorrect,
+            "too-high" => Outcome::TooHigh,
+            "too-low" => Outcome::TooLow,
+            "rate-limited" => Outcome::RateLimited,
+            "unknown" => Outcome::Unknown,
+            _ => return Err(format!("unknown outcome: {}", s)),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Answer,
+    pub outcome: Outcome,
+    pub at: Timestamp,
+}
+
+impl Attempt {
+    /// One tab separated line. The answer is JSON quoted, so tabs and the newlines of a
+    /// grid answer stay inside its field.
+    fn to_line(&self) -> String {
+        let answer = serde_json::to_string(&self.answer.to_string()).unwrap();
+        format!(
+            "{}\t{}\t{}\t{}\t{}",
+            self.day, self.part, answer, self.outcome, self.at
+        )
+    }
+
+    fn from_line(line: &str) -> Option<Attempt> {
+        let mut fields = line.split('\t');
+        let attempt = Attempt {
+            day: fields.next()?.parse().ok()?,
+            part: fields.next()?.parse().ok()?,
+            answer: serde_json::from_str::<String>(fields.next()?)
+                .ok()?
+                .parse()
+                .ok()?,
+            outcome: fields.next()?.parse().ok()?,
+            at: fields.next()?.parse().ok()?,
+        };
+        fields.next().is_none().then_some(attempt)
+    }
+}
+
+/// Why an answer was not sent to the server.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved(Answer),
+    KnownWrong(Outcome),
+    AboveTooHigh(Answer),
+    BelowTooLow(Answer),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved(answer) => write!(f, "already solved with {}", answer),
+            Refusal::KnownWrong(outcome) => write!(f, "already submitted, it was {}", outcome),
+            Refusal::AboveTooHigh(bound) => write!(f, "{} was already too high", bound),
+            Refusal::BelowTooLow(bound) => write!(f, "{} was already too low", bound),
+        }
+    }
+}
+
+/// Every answer submitted for a year, stored as tab separated lines.
+pub struct Ledger {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+impl Ledger {
+    pub fn path(dir: &Path, year: u32) -> PathBuf {
+        dir.join(format!("{year}-ledger.tsv"))
+    }
+
+    /// Loads the ledger at `path`, starting an empty one if it does not exist yet.
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let attempts = match std::fs::read_to_string(path) {
+            Ok(content) => content
+                .lines()
+                .map(|line| {
+                    Attempt::from_line(line).ok_or_else(|| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!("malformed ledger line: {}", line),
+                        )
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        Ok(Self {
+            path: path.to_owned(),
+            attempts,
+        })
+    }
+
+    pub fn attempts(&self, day: u8, part: u8) -> impl Iterator<Item = &Attempt> {
+        self.attempts
+            .iter()
+            .filter(move |a| a.day == day && a.part == part)
+    }
+
+    /// Checks an answer against what earlier attempts already ruled out.
+    pub fn check(&self, day: u8, part: u8, answer: &Answer) -> Result<(), Refusal> {
+        if let Some(correct) = self
+            .attempts(day, part)
+            .find(|a| a.outcome == Outcome::Correct)
+        {
+            return Err(Refusal::AlreadySolved(correct.answer.clone()));
+        }
+        if let Some(wrong) = self
+            .attempts(day, part)
+            .find(|a| a.answer == *answer && a.outcome.is_wrong())
+        {
+            return Err(Refusal::KnownWrong(wrong.outcome));
+        }
+
+        // Only numbers are ordered, so text answers never fall outside a bound.
+        let bound = |outcome| {
+            self.attempts(day, part)
+                .filter(move |a| a.outcome == outcome)
+                .map(|a| &a.answer)
+        };
+        if let Some(high) = bound(Outcome::TooHigh)
+            .filter(|&high| answer >= high)
+            .min_by(|a, b| a.partial_cmp(b).unwrap())
+        {
+            return Err(Refusal::AboveTooHigh(high.clone()));
+        }
+        if let Some(low) = bound(Outcome::TooLow)
+            .filter(|&low| answer <= low)
+            .max_by(|a, b| a.partial_cmp(b).unwrap())
+        {
+            return Err(Refusal::BelowTooLow(low.clone()));
+        }
+        Ok(())
+    }
+
+    /// Appends the attempt to the ledger file.
+    pub fn record(&mut self, attempt: Attempt) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", attempt.to_line())?;
+        self.attempts.push(attempt);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::files::temp_path;
+
+    fn answer(s: &str) -> Answer {
+        s.parse().unwrap()
+    }
+
+    fn attempt(answer: &str, outcome: Outcome) -> Attempt {
+        Attempt {
+            day: 1,
+            part: 1,
+            answer: answer.parse().unwrap(),
+            outcome,
+            at: Timestamp::UNIX_EPOCH,
+        }
+    }
+
+    fn ledger(attempts: Vec<Attempt>) -> Ledger {
+        Ledger {
+            path: PathBuf::new(),
+            attempts,
+        }
+    }
+
+    #[test]
+    fn test_from_response() {
+        let page = "<p>That's not the right answer; your answer is too high. If you're stuck";
+        assert_eq!(Outcome::from_response(page), Some(Outcome::TooHigh));
+        let page = "<p>That's the right answer! You are one gold star closer";
+        assert_eq!(Outcome::from_response(page), Some(Outcome::Correct));
+        let page = "<p>You gave an answer too recently; you have to wait";
+        assert_eq!(Outcome::from_response(page), Some(Outcome::RateLimited));
+        let page = "<p>That's not the right answer. If you're stuck";
+        assert_eq!(Outcome::from_response(page), Some(Outcome::Incorrect));
+        assert_eq!(Outcome::from_response("<html></html>"), None);
+    }
+
+    #[test]
+    fn test_refuse_known_wrong() {
+        let ledger = ledger(vec![
+            attempt("abc", Outcome::Incorrect),
+            attempt("42", Outcome::RateLimited),
+        ]);
+        assert_eq!(
+            ledger.check(1, 1, &answer("abc")),
+            Err(Refusal::KnownWrong(Outcome::Incorrect))
+        );
+        assert_eq!(ledger.check(1, 1, &answer("42")), Ok(()));
+        assert_eq!(ledger.check(1, 2, &answer("abc")), Ok(()));
+    }
+
+    #[test]
+    fn test_refuse_outside_bracket() {
+        let ledger = ledger(vec![
+            attempt("100", Outcome::TooHigh),
+            attempt("200", Outcome::TooHigh),
+            attempt("10", Outcome::TooLow),
+        ]);
+        assert_eq!(
+            ledger.check(1, 1, &answer("150")),
+            Err(Refusal::AboveTooHigh(answer("100")))
+        );
+        assert_eq!(
+            ledger.check(1, 1, &answer("5")),
+            Err(Refusal::BelowTooLow(answer("10")))
+        );
+        assert_eq!(ledger.check(1, 1, &answer("11")), Ok(()));
+        assert_eq!(ledger.check(1, 1, &answer("99")), Ok(()));
+        assert_eq!(ledger.check(1, 1, &answer("abc")), Ok(()));
+    }
+
+    #[test]
+    fn test_refuse_solved() {
+        let ledger = ledger(vec![attempt("7", Outcome::Correct)]);
+        assert_eq!(
+            ledger.check(1, 1, &answer("8")),
+            Err(Refusal::AlreadySolved(answer("7")))
+        );
+    }
+
+    #[test]
+    fn test_record_round_trip() {
+        let path = temp_path("ledger.tsv");
+        let mut ledger = Ledger::open(&path).unwrap();
+        ledger.record(attempt("100", Outcome::TooHigh)).unwrap();
+        ledger.record(attempt("10", Outcome::TooLow)).unwrap();
+
+        let reopened = Ledger::open(&path).unwrap();
+        assert_eq!(reopened.attempts, ledger.attempts);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_unknown_outcome() {
+        assert_eq!("unknown".parse(), Ok(Outcome::Unknown));
+        let ledger = ledger(vec![attempt("42", Outcome::Unknown)]);
+        assert_eq!(ledger.check(1, 1, &answer("42")), Ok(()));
+    }
+
+    #[test]
+    fn test_record_round_trip_escaped() {
+        let path = temp_path("ledger-escaped.tsv");
+        let mut ledger = Ledger::open(&path).unwrap();
+        ledger
+            .record(attempt("#..#\n####\n#..#", Outcome::Incorrect))
+            .unwrap();
+        ledger
+            .record(attempt("a\tb \"c\"", Outcome::Incorrect))
+            .unwrap();
+        assert_eq!(
+            ledger.attempts[0].answer,
+            Answer::Grid("#..#\n####\n#..#".to_owned())
+        );
+
+        let reopened = Ledger::open(&path).unwrap();
+        assert_eq!(reopened.attempts, ledger.attempts);
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(
+            Attempt::from_line("1\t1\t100\ttoo-high\t1970-01-01T00:00:00Z"),
+            None
+        );
+    }
+}
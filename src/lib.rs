@@ -1,16 +1,23 @@
-use nom::{error::Error, Err as NomErr};
-
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
-mod day11;
+use std::collections::BTreeMap;
+
+use nom::Parser;
+
+use crate::{
+    parse::{ParseError, Span},
+    solution::Solve,
+};
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
 
 extern crate aoc_runner;
 
@@ -20,26 +27,43 @@ extern crate aoc_runner_derive;
 aoc_lib! { year = 2025 }
 
 pub mod grid;
+pub mod ocr;
+pub mod parse;
+pub mod parsers;
+pub mod point;
+pub mod registry;
+pub mod solution;
 
-pub type IResult<I, O, E = nom::error::Error<I>> = Result<(I, O), nom::Err<E>>;
+/// The solution table of every year, the one in `aoc_lib!` and those in `src/yYYYY/`.
+const YEARS: &[(u32, registry::Solutions)] = &[
+    (YEAR, registry::SOLUTIONS),
+    // the scaffold command adds the other years here
+];
 
-pub fn convert_iresult_to_owned<O>(res: IResult<&str, O>) -> Result<O, NomErr<Error<String>>> {
-    match res {
-        Ok((_, o)) => Ok(o),
-        Err(e) => Err(convert_error_to_owned(e)),
-    }
+/// Every solution in the crate by year and day.
+pub fn registry() -> BTreeMap<(u32, u8), &'static dyn Solve> {
+    YEARS
+        .iter()
+        .flat_map(|&(year, solutions)| {
+            solutions
+                .iter()
+                .map(move |&(day, solution)| ((year, day), solution))
+        })
+        .collect()
 }
 
-fn convert_error_to_owned(e: NomErr<Error<&str>>) -> NomErr<Error<String>> {
-    match e {
-        NomErr::Incomplete(needed) => NomErr::Incomplete(needed),
-        NomErr::Error(err) => NomErr::Error(Error {
-            input: err.input.to_owned(),
-            code: err.code,
-        }),
-        NomErr::Failure(err) => NomErr::Failure(Error {
-            input: err.input.to_owned(),
-            code: err.code,
-        }),
+pub type IResult<I, O, E = ParseError> = Result<(I, O), nom::Err<E>>;
+
+/// Runs `parser` over the whole of `input`. Anything but a single trailing newline left
+/// unparsed is an error pointing at the leftover.
+pub fn parse_all<'a, O>(
+    mut parser: impl Parser<Span<'a>, Output = O, Error = ParseError>,
+    input: &'a str,
+) -> Result<O, ParseError> {
+    let (rest, output) = parser.parse(Span::new(input))?;
+    if rest.fragment().is_empty() || *rest.fragment() == "\n" {
+        Ok(output)
+    } else {
+        Err(ParseError::leftover(rest))
     }
 }
@@ -1,36 +1,605 @@
-use std::path::PathBuf;
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
 
-use askama::Template;
-use clap::Parser;
+use clap::{Args, Parser, Subcommand};
+
+mod answers;
+mod bench;
+mod calendar;
+mod client;
+mod files;
+mod input;
+mod ledger;
+mod puzzle;
+mod runner;
+mod scaffold;
+mod watch;
+
+use answers::{Answers, Verdict};
+use aoc::solution::Answer;
+use bench::{Baseline, Plan};
+use calendar::Puzzle;
+use client::Client;
+use input::{Fetch, InputStore};
+use ledger::{Attempt, Ledger, Outcome};
+use runner::{DayJob, Execution, Format, Record, Selection, Status};
+use scaffold::{DayInfo, Kind, Layout};
 
 #[derive(Parser)]
+#[command(args_conflicts_with_subcommands = true)]
 struct Cli {
-    /// Day of month. Defaults to today's day.
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    scaffold: ScaffoldArgs,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Download the puzzle input, unless it is already cached.
+    Input(InputArgs),
+    /// Submit an answer, unless earlier attempts already rule it out.
+    Submit(SubmitArgs),
+    /// Run solvers on the cached puzzle inputs and time them.
+    Run(RunArgs),
+    /// Check solvers against the accepted answers on the cached puzzle inputs.
+    Verify(VerifyArgs),
+    /// Time solvers repeatedly and compare them against a stored baseline.
+    Bench(BenchArgs),
+    /// Rebuild, test and run a day every time its source or input changes.
+    Watch(WatchArgs),
+}
+
+#[derive(Args)]
+struct DateArgs {
+    /// Day of month. Defaults to the latest unlocked puzzle during the event.
     day: Option<u8>,
+
+    /// Event year. Defaults to the latest event.
+    #[arg(long)]
+    year: Option<u32>,
+}
+
+fn resolve_puzzle(year: Option<u32>, day: Option<u8>) -> Puzzle {
+    calendar::puzzle_date(&jiff::Zoned::now())
+        .map_err(|e| e.to_string())
+        .and_then(|today| calendar::resolve(year, day, today).map_err(|e| e.to_string()))
+        .unwrap_or_else(|e| fail(e))
+}
+
+impl DateArgs {
+    fn resolve(&self) -> Puzzle {
+        resolve_puzzle(self.year, self.day)
+    }
+}
+
+#[derive(Args)]
+struct ScaffoldArgs {
+    #[command(flatten)]
+    date: DateArgs,
+
+    /// Sleep until the next puzzle unlocks, then scaffold it and fetch its input.
+    #[arg(long, conflicts_with_all = ["day", "year"])]
+    wait: bool,
+
+    /// Directory inputs are cached in when waiting for a puzzle.
+    #[arg(long, default_value = "input")]
+    input_dir: PathBuf,
+
+    /// Shape of the puzzle input, selects the starting template.
+    #[arg(long, value_enum, default_value_t = Kind::Lines)]
+    kind: Kind,
+
+    /// Print what would change instead of writing any files.
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Saved puzzle description to take the example and answers from.
+    #[arg(long, conflicts_with = "fetch_puzzle")]
+    puzzle: Option<PathBuf>,
+
+    /// Download the puzzle description to take the example and answers from.
+    #[arg(long)]
+    fetch_puzzle: bool,
+
+    #[command(flatten)]
+    server: ServerArgs,
+}
+
+#[derive(Args)]
+struct ServerArgs {
+    /// Base URL of the puzzle server.
+    #[arg(long, env = "AOC_BASE_URL", default_value = "https://adventofcode.com")]
+    base_url: String,
+
+    /// File containing the value of the `session` cookie.
+    #[arg(long, env = "AOC_SESSION_FILE", default_value = ".session")]
+    session_file: PathBuf,
+}
+
+#[derive(Args)]
+struct InputArgs {
+    #[command(flatten)]
+    date: DateArgs,
+
+    /// Directory inputs are cached in, one subdirectory per year.
+    #[arg(long, default_value = "input")]
+    input_dir: PathBuf,
+
+    #[command(flatten)]
+    server: ServerArgs,
 }
 
-#[derive(Template)]
-#[template(path = "dayXX.rs.askama")]
-struct DayXXTemplate {
+#[derive(Args)]
+struct SubmitArgs {
+    /// Day of month.
     day: u8,
+
+    /// Puzzle part, 1 or 2.
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+
+    answer: String,
+
+    /// Event year. Defaults to the latest event.
+    #[arg(long)]
+    year: Option<u32>,
+
+    /// Directory holding the answer ledger and the accepted answers.
+    #[arg(long, default_value = "answers")]
+    ledger_dir: PathBuf,
+
+    #[command(flatten)]
+    server: ServerArgs,
+}
+
+#[derive(Args)]
+struct ExecutionArgs {
+    /// Days to run at the same time. Defaults to the number of CPUs.
+    ///
+    /// Parts that timed out are not counted, they keep running next to these days.
+    #[arg(long)]
+    jobs: Option<usize>,
+
+    /// Time a day may take before its unfinished parts are reported as timed out, like `30s`.
+    ///
+    /// A timed out part cannot be stopped. It keeps using a CPU until it finishes or the run
+    /// ends, on top of the `--jobs` days, so timings of the days after it can be slower.
+    #[arg(long)]
+    timeout: Option<jiff::SignedDuration>,
+}
+
+impl ExecutionArgs {
+    fn threads(&self) -> usize {
+        self.jobs.unwrap_or_else(|| {
+            std::thread::available_parallelism().map_or(1, |threads| threads.get())
+        })
+    }
+
+    fn timeout(&self) -> Option<std::time::Duration> {
+        self.timeout.map(|timeout| timeout.unsigned_abs())
+    }
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// Days to run, like `5`, `5:2`, `1..=5` or `1..6:1`. Defaults to every day.
+    selections: Vec<Selection>,
+
+    /// Output format, `json` prints one JSON object per line for every part.
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Directory inputs are cached in, one subdirectory per year.
+    #[arg(long, default_value = "input")]
+    input_dir: PathBuf,
+
+    #[command(flatten)]
+    execution: ExecutionArgs,
+}
+
+#[derive(Args)]
+struct VerifyArgs {
+    /// Days to check, like `5`, `5:2`, `1..=5` or `1..6:1`. Defaults to every day.
+    selections: Vec<Selection>,
+
+    /// Directory inputs are cached in, one subdirectory per year.
+    #[arg(long, default_value = "input")]
+    input_dir: PathBuf,
+
+    /// Directory holding the accepted answers.
+    #[arg(long, default_value = "answers")]
+    answers_dir: PathBuf,
+
+    #[command(flatten)]
+    execution: ExecutionArgs,
+}
+
+#[derive(Args)]
+struct BenchArgs {
+    /// Days to benchmark, like `5`, `5:2`, `1..=5` or `1..6:1`. Defaults to every day.
+    selections: Vec<Selection>,
+
+    /// Directory inputs are cached in, one subdirectory per year.
+    #[arg(long, default_value = "input")]
+    input_dir: PathBuf,
+
+    /// Untimed runs before measuring.
+    #[arg(long, default_value_t = 3)]
+    warmup: usize,
+
+    /// Timed runs to take statistics over.
+    #[arg(long, default_value_t = 20)]
+    samples: usize,
+
+    /// Directory holding the baseline timings.
+    #[arg(long, default_value = "bench")]
+    baseline_dir: PathBuf,
+
+    /// Percent a median may slow down compared to the baseline before it is flagged.
+    #[arg(long, default_value_t = 10.0)]
+    threshold: f64,
+
+    /// Store the medians of this run as the new baseline.
+    #[arg(long)]
+    save_baseline: bool,
+}
+
+#[derive(Args)]
+struct WatchArgs {
+    /// Day of month. Defaults to the latest unlocked puzzle during the event.
+    day: Option<u8>,
+
+    /// Directory inputs are cached in, one subdirectory per year.
+    #[arg(long, default_value = "input")]
+    input_dir: PathBuf,
+}
+
+fn fail(message: impl Display) -> ! {
+    eprintln!("{}", message);
+    std::process::exit(1);
 }
 
 fn main() {
     let cli = Cli::parse();
 
-    let day = match cli.day {
-        Some(d) => d,
-        None => jiff::Zoned::now().day() as u8,
-    };
-    let path = PathBuf::from("src").join(format!("day{:02}.rs", day));
+    match cli.command {
+        None => scaffold_day(cli.scaffold),
+        Some(Command::Input(args)) => fetch_input(args),
+        Some(Command::Submit(args)) => submit_answer(args),
+        Some(Command::Run(args)) => run_solvers(args),
+        Some(Command::Verify(args)) => verify_answers(args),
+        Some(Command::Bench(args)) => bench_solvers(args),
+        Some(Command::Watch(args)) => watch_day(args),
+    }
+}
+
+fn scaffold_day(args: ScaffoldArgs) {
+    if !args.wait {
+        scaffold_puzzle(&args, args.date.resolve());
+        return;
+    }
+
+    let puzzle = wait_for_unlock();
+    scaffold_puzzle(&args, puzzle);
+    if !args.dry_run {
+        fetch_puzzle_input(puzzle, &args.input_dir, &args.server);
+    }
+}
+
+fn wait_for_unlock() -> Puzzle {
+    let (puzzle, unlock) = calendar::next_unlock(&jiff::Zoned::now()).unwrap_or_else(|e| fail(e));
+    println!(
+        "Waiting for {} day {} to unlock at {}",
+        puzzle.year,
+        puzzle.day,
+        unlock.with_time_zone(jiff::tz::TimeZone::system())
+    );
+    loop {
+        let remaining = jiff::Timestamp::now().duration_until(unlock.timestamp());
+        if remaining.is_zero() || remaining.is_negative() {
+            return puzzle;
+        }
+        std::thread::sleep(remaining.unsigned_abs());
+    }
+}
+
+fn scaffold_puzzle(args: &ScaffoldArgs, Puzzle { year, day }: Puzzle) {
+    let layout = Layout::new(year, day, aoc::YEAR);
+    let path = layout.day_path();
 
     if !path.exists() {
-        let template = DayXXTemplate { day };
-        let rendered = template.render().unwrap();
+        if args.dry_run {
+            println!("Would create file: {}", path.display());
+        } else {
+            let mut info =
+                DayInfo::new(day, args.kind).with_examples(puzzle_examples(args, year, day));
+            if !layout.uses_aoc_runner() {
+                info = info.without_aoc_runner();
+            }
+            let rendered = scaffold::render_day(args.kind, &info);
 
-        std::fs::write(&path, rendered).expect("Unable to write file");
-        println!("Created file: {}", path.display());
+            std::fs::create_dir_all(path.parent().unwrap()).expect("Unable to create directory");
+            std::fs::write(&path, rendered).expect("Unable to write file");
+            println!("Created file: {}", path.display());
+        }
     } else {
         println!("File already exists: {}", path.display());
     }
+
+    let year_path = layout.year_path();
+    if let Some(year_path) = year_path.as_ref().filter(|path| !path.exists()) {
+        if args.dry_run {
+            println!("Would create file: {}", year_path.display());
+        } else {
+            std::fs::write(year_path, scaffold::render_year(year)).expect("Unable to write file");
+            println!("Created file: {}", year_path.display());
+        }
+    }
+
+    for (file, declaration) in layout.registrations() {
+        let source = match std::fs::read_to_string(&file) {
+            Ok(source) => source,
+            // a dry run has not created the year module
+            Err(e)
+                if e.kind() == std::io::ErrorKind::NotFound
+                    && Some(&file) == year_path.as_ref() =>
+            {
+                scaffold::render_year(year)
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => fail(format!("Unable to read {}: {}", file.display(), e)),
+        };
+        match scaffold::register_module(&source, &declaration) {
+            Ok(insertion) if args.dry_run => {
+                print!("{}", insertion.diff(&file.display().to_string()))
+            }
+            Ok(insertion) => {
+                std::fs::write(&file, insertion.source).expect("Unable to write module file");
+                println!("Added {} to {}", declaration.line(), file.display());
+            }
+            Err(e) => println!("Not registering in {}: {}", file.display(), e),
+        }
+    }
+}
+
+fn puzzle_examples(args: &ScaffoldArgs, year: u32, day: u8) -> Vec<puzzle::Example> {
+    let html = if let Some(path) = &args.puzzle {
+        std::fs::read_to_string(path).expect("Unable to read puzzle description")
+    } else if args.fetch_puzzle {
+        Client::from_session_file(&args.server.base_url, &args.server.session_file)
+            .and_then(|client| client.puzzle(year, day))
+            .unwrap_or_else(|e| fail(format!("Unable to fetch puzzle description: {}", e)))
+    } else {
+        return Vec::new();
+    };
+
+    let examples = puzzle::examples(&html);
+    if examples.is_empty() {
+        println!("No examples found in the puzzle description");
+    }
+    examples
+}
+
+fn fetch_input(args: InputArgs) {
+    fetch_puzzle_input(args.date.resolve(), &args.input_dir, &args.server);
+}
+
+fn fetch_puzzle_input(Puzzle { year, day }: Puzzle, input_dir: &Path, server: &ServerArgs) {
+    let store = InputStore::new(input_dir);
+    let path = store.path(year, day);
+    if path.exists() {
+        println!("Input already exists: {}", path.display());
+        return;
+    }
+
+    let fetched = Client::from_session_file(&server.base_url, &server.session_file)
+        .and_then(|client| store.fetch(&client, year, day));
+    match fetched {
+        Ok(Fetch::Downloaded(path)) => println!("Downloaded input: {}", path.display()),
+        Ok(Fetch::Cached(path)) => println!("Input already exists: {}", path.display()),
+        Err(e) => fail(format!("Unable to fetch input for day {}: {}", day, e)),
+    }
+}
+
+fn submit_answer(args: SubmitArgs) {
+    let Puzzle { year, day } = resolve_puzzle(args.year, Some(args.day));
+    let (part, answer) = (args.part, args.answer.parse::<Answer>().unwrap());
+    let mut ledger =
+        Ledger::open(&Ledger::path(&args.ledger_dir, year)).expect("Unable to read ledger");
+    if let Err(refusal) = ledger.check(day, part, &answer) {
+        fail(format!(
+            "Not submitting {} for day {} part {}: {}",
+            answer, day, part, refusal
+        ));
+    }
+
+    let page = Client::from_session_file(&args.server.base_url, &args.server.session_file)
+        .and_then(|client| client.submit(year, day, part, &answer.to_string()))
+        .unwrap_or_else(|e| fail(format!("Unable to submit answer: {}", e)));
+    // the attempt is recorded even when the reply is not understood, it was still sent
+    let outcome = Outcome::from_response(&page).unwrap_or(Outcome::Unknown);
+
+    ledger
+        .record(Attempt {
+            day,
+            part,
+            answer: answer.clone(),
+            outcome,
+            at: jiff::Timestamp::now(),
+        })
+        .expect("Unable to write ledger");
+    if outcome == Outcome::Unknown {
+        fail(format!("Unrecognised response:\n{}", page));
+    }
+    println!("Day {} part {}: {} is {}", day, part, answer, outcome);
+
+    if outcome == Outcome::Correct {
+        Answers::open(&Answers::path(&args.ledger_dir, year))
+            .and_then(|mut answers| answers.insert(day, part, &answer))
+            .expect("Unable to write accepted answers");
+    }
+}
+
+/// Groups the selected solvers by day, together with the day's cached input.
+fn day_jobs(selections: &[Selection], input_dir: &Path) -> Vec<DayJob> {
+    let store = InputStore::new(input_dir);
+    let solvers = runner::select(selections);
+    if solvers.is_empty() {
+        fail("No solvers match the selection");
+    }
+
+    solvers
+        .chunk_by(|a, b| a.day == b.day)
+        .map(|solvers| {
+            let day = solvers[0].day;
+            let path = store.path(aoc::YEAR, day);
+            let input = match std::fs::read_to_string(&path) {
+                Ok(input) => Some(input.into()),
+                Err(e) => {
+                    eprintln!("Day {}: unable to read {}: {}", day, path.display(), e);
+                    None
+                }
+            };
+            DayJob {
+                solvers: solvers.to_vec(),
+                input,
+            }
+        })
+        .collect()
+}
+
+fn run_solvers(args: RunArgs) {
+    let jobs = day_jobs(&args.selections, &args.input_dir);
+    let (threads, timeout) = (args.execution.threads(), args.execution.timeout());
+    match args.format {
+        Format::Text => runner::execute(
+            jobs,
+            threads,
+            timeout,
+            runner::run,
+            |solver, run| match run {
+                Execution::Done(report) => println!("{}", report),
+                Execution::MissingInput => {}
+                Execution::TimedOut(limit) => println!(
+                    "Day {} - Part {}: timed out after {:?}",
+                    solver.day, solver.part, limit
+                ),
+            },
+        ),
+        Format::Json => runner::execute(jobs, threads, timeout, Record::new, |solver, run| {
+            let record = match run {
+                Execution::Done(record) => record,
+                Execution::MissingInput => Record::unfinished(solver, Status::MissingInput),
+                Execution::TimedOut(_) => Record::unfinished(solver, Status::TimedOut),
+            };
+            println!("{}", record);
+        }),
+    }
+}
+
+fn verify_answers(args: VerifyArgs) {
+    let answers = Answers::open(&Answers::path(&args.answers_dir, aoc::YEAR))
+        .unwrap_or_else(|e| fail(format!("Unable to read accepted answers: {}", e)));
+    let jobs = day_jobs(&args.selections, &args.input_dir);
+    let (threads, timeout) = (args.execution.threads(), args.execution.timeout());
+
+    let (mut passed, mut failed, mut missing, mut without_input) = (0, 0, 0, 0);
+    runner::execute(jobs, threads, timeout, runner::run, |solver, run| {
+        let (day, part) = (solver.day, solver.part);
+        let answer = match run {
+            Execution::Done(report) => report.answer,
+            Execution::MissingInput => {
+                without_input += 1;
+                println!("Day {} - Part {}: missing input", day, part);
+                return;
+            }
+            Execution::TimedOut(limit) => Err(format!("timed out after {:?}", limit)),
+        };
+        match answer {
+            Ok(answer) => {
+                let verdict = answers.verdict(day, part, &answer);
+                match verdict {
+                    Verdict::Pass => passed += 1,
+                    Verdict::Fail { .. } => failed += 1,
+                    Verdict::Missing => missing += 1,
+                }
+                println!("Day {} - Part {}: {} ({})", day, part, answer, verdict);
+            }
+            Err(e) => {
+                failed += 1;
+                println!("Day {} - Part {}: FAIL, {}", day, part, e);
+            }
+        }
+    });
+
+    println!(
+        "{} passed, {} failed, {} missing, {} without input",
+        passed, failed, missing, without_input
+    );
+    // an answer that could not be checked for lack of input does not count as verified
+    if failed > 0 || without_input > 0 {
+        std::process::exit(1);
+    }
+}
+
+fn bench_solvers(args: BenchArgs) {
+    let mut baseline = Baseline::open(&Baseline::path(&args.baseline_dir, aoc::YEAR))
+        .unwrap_or_else(|e| fail(format!("Unable to read baseline: {}", e)));
+    let plan = Plan {
+        warmup: args.warmup,
+        samples: args.samples,
+    };
+
+    // One part at a time, so solvers do not compete with each other for the CPU.
+    let mut regressed = 0;
+    let jobs = day_jobs(&args.selections, &args.input_dir);
+    for (solver, input) in jobs.iter().flat_map(|job| {
+        let input = job.input.as_deref();
+        job.solvers
+            .iter()
+            .filter_map(move |solver| Some((solver, input?)))
+    }) {
+        let bench = match bench::bench(solver, input, plan) {
+            Ok(bench) => bench,
+            Err(e) => {
+                println!("Day {} - Part {}: failed: {}", solver.day, solver.part, e);
+                continue;
+            }
+        };
+        println!("Day {} - Part {}:", bench.day, bench.part);
+        println!("\tgenerator: {}", bench.generator);
+        println!("\tsolver: {}", bench.solver);
+        for regression in baseline.regressions(&bench, args.threshold) {
+            regressed += 1;
+            println!("\t{}", regression);
+        }
+        if args.save_baseline {
+            baseline.insert(&bench);
+        }
+    }
+
+    if args.save_baseline {
+        baseline
+            .save()
+            .unwrap_or_else(|e| fail(format!("Unable to write baseline: {}", e)));
+    }
+    if regressed > 0 {
+        fail(format!(
+            "{} regressions beyond {}%",
+            regressed, args.threshold
+        ));
+    }
+}
+
+fn watch_day(args: WatchArgs) {
+    let Puzzle { year, day } = resolve_puzzle(Some(aoc::YEAR), args.day);
+    let paths = [
+        Layout::new(year, day, aoc::YEAR).day_path(),
+        InputStore::new(&args.input_dir).path(year, day),
+    ];
+    watch::watch(day, &paths, &args.input_dir);
 }
@@ -0,0 +1,297 @@
+use std::fmt::Display;
+
+use crate::grid::Grid;
+
+/// Letters of the font six pixels high, most of them four wide.
+const FONT_6: &[(char, &str)] = &[
+    ('A', ".##.|#..#|#..#|####|#..#|#..#"),
+    ('B', "###.|#..#|###.|#..#|#..#|###."),
+    ('C', ".##.|#..#|#...|#...|#..#|.##."),
+    ('E', "####|#...|###.|#...|#...|####"),
+    ('F', "####|#...|###.|#...|#...|#..."),
+    ('G', ".##.|#..#|#...|#.##|#..#|.###"),
+    ('H', "#..#|#..#|####|#..#|#..#|#..#"),
+    ('I', "###|.#.|.#.|.#.|.#.|###"),
+    ('J', "..##|...#|...#|...#|#..#|.##."),
+    ('K', "#..#|#.#.|##..|#.#.|#.#.|#..#"),
+    ('L', "#...|#...|#...|#...|#...|####"),
+    ('O', ".##.|#..#|#..#|#..#|#..#|.##."),
+    ('P', "###.|#..#|#..#|###.|#...|#..."),
+    ('R', "###.|#..#|#..#|###.|#.#.|#..#"),
+    ('S', ".###|#...|#...|.##.|...#|###."),
+    ('U', "#..#|#..#|#..#|#..#|#..#|.##."),
+    ('Y', "#...#|#...#|.#.#.|..#..|..#..|..#.."),
+    ('Z', "####|...#|..#.|.#..|#...|####"),
+];
+
+/// Letters of the font ten pixels high and six wide.
+const FONT_10: &[(char, &str)] = &[
+    (
+        'A',
+        "..##..|.#..#.|#....#|#....#|#....#|######|#....#|#....#|#....#|#....#",
+    ),
+    (
+        'B',
+        "#####.|#....#|#....#|#....#|#####.|#....#|#....#|#....#|#....#|#####.",
+    ),
+    (
+        'C',
+        ".####.|#....#|#.....|#.....|#.....|#.....|#.....|#.....|#....#|.####.",
+    ),
+    (
+        'E',
+        "######|#.....|#.....|#.....|#####.|#.....|#.....|#.....|#.....|######",
+    ),
+    (
+        'F',
+        "######|#.....|#.....|#.....|#####.|#.....|#.....|#.....|#.....|#.....",
+    ),
+    (
+        'G',
+        ".####.|#....#|#.....|#.....|#.....|#..###|#....#|#....#|#...##|.###.#",
+    ),
+    (
+        'H',
+        "#....#|#....#|#....#|#....#|######|#....#|#....#|#....#|#....#|#....#",
+    ),
+    (
+        'J',
+        "...###|....#.|....#.|....#.|....#.|....#.|....#.|#...#.|#...#.|.###..",
+    ),
+    (
+        'K',
+        "#....#|#...#.|#..#..|#.#...|##....|##....|#.#...|#..#..|#...#.|#....#",
+    ),
+    (
+        'L',
+        "#.....|#.....|#.....|#.....|#.....|#.....|#.....|#.....|#.....|######",
+    ),
+    (
+        'N',
+        "#....#|##...#|##...#|#.#..#|#.#..#|#..#.#|#..#.#|#...##|#...##|#....#",
+    ),
+    (
+        'P',
+        "#####.|#....#|#....#|#....#|#####.|#.....|#.....|#.....|#.....|#.....",
+    ),
+    (
+        'R',
+        "#####.|#....#|#....#|#....#|#####.|#..#..|#...#.|#...#.|#....#|#....#",
+    ),
+    (
+        'X',
+        "#....#|#....#|.#..#.|.#..#.|..##..|..##..|.#..#.|.#..#.|#....#|#....#",
+    ),
+    (
+        'Z',
+        "######|.....#|.....#|....#.|...#..|..#...|.#....|#.....|#.....|######",
+    ),
+];
+
+/// A glyph that matches no letter of the font, drawn with `#` and `.`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownGlyph {
+    /// Position of the glyph among all glyphs, counting from 0.
+    pub index: usize,
+    pub rows: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OcrError {
+    /// Only fonts six and ten pixels high are known.
+    Height(usize),
+    /// The text read so far, with `?` for every unknown glyph, and those glyphs.
+    UnknownGlyphs(String, Vec<UnknownGlyph>),
+}
+
+impl Display for OcrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OcrError::Height(height) => write!(
+                f,
+                "letters are {} pixels high, only 6 and 10 are known",
+                height
+            ),
+            OcrError::UnknownGlyphs(text, glyphs) => {
+                write!(f, "unknown glyphs in {:?}:", text)?;
+                for glyph in glyphs {
+                    write!(f, "\nglyph {}:\n{}", glyph.index, glyph.rows.join("\n"))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for OcrError {}
+
+fn render(rows: &[Vec<bool>], columns: std::ops::Range<usize>) -> Vec<String> {
+    rows.iter()
+        .map(|row| {
+            columns
+                .clone()
+                .map(|x| if row[x] { '#' } else { '.' })
+                .collect()
+        })
+        .collect()
+}
+
+/// Reads letters from rows of pixels, `true` for lit. Glyphs are split on blank columns.
+pub fn read_pixels(rows: &[Vec<bool>]) -> Result<String, OcrError> {
+    let blank = |row: &&Vec<bool>| !row.iter().any(|&lit| lit);
+    let start = rows.iter().position(|row| !blank(&row)).unwrap_or(0);
+    let end = rows
+        .iter()
+        .rposition(|row| !blank(&row))
+        .map_or(0, |i| i + 1);
+    let rows = &rows[start..end.max(start)];
+
+    let font = match rows.len() {
+        6 => FONT_6,
+        10 => FONT_10,
+        height => return Err(OcrError::Height(height)),
+    };
+
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let rows: Vec<Vec<bool>> = rows
+        .iter()
+        .map(|row| (0..width).map(|x| row.get(x) == Some(&true)).collect())
+        .collect();
+    let lit_column = |x: usize| rows.iter().any(|row| row[x]);
+
+    let mut text = String::new();
+    let mut unknown = Vec::new();
+    let mut x = 0;
+    while x < width {
+        if !lit_column(x) {
+            x += 1;
+            continue;
+        }
+        let start = x;
+        while x < width && lit_column(x) {
+            x += 1;
+        }
+
+        let glyph = render(&rows, start..x);
+        let pattern = glyph.join("|");
+        match font.iter().find(|(_, letter)| *letter == pattern) {
+            Some(&(letter, _)) => text.push(letter),
+            None => {
+                unknown.push(UnknownGlyph {
+                    index: text.chars().count(),
+                    rows: glyph,
+                });
+                text.push('?');
+            }
+        }
+    }
+
+    if unknown.is_empty() {
+        Ok(text)
+    } else {
+        Err(OcrError::UnknownGlyphs(text, unknown))
+    }
+}
+
+/// Reads letters drawn with `#` or `█`, any other character is blank.
+pub fn read(rendered: &str) -> Result<String, OcrError> {
+    let rows: Vec<Vec<bool>> = rendered
+        .lines()
+        .map(|line| line.chars().map(|c| c == '#' || c == '█').collect())
+        .collect();
+    read_pixels(&rows)
+}
+
+/// Reads letters from anything that displays as `#` for lit pixels, see [`read`].
+pub fn read_display(grid: &impl Display) -> Result<String, OcrError> {
+    read(&grid.to_string())
+}
+
+/// Reads letters from a grid with `true` for lit pixels.
+pub fn read_grid(grid: &Grid<bool>) -> Result<String, OcrError> {
+    let rows: Vec<Vec<bool>> = grid.rows().map(<[bool]>::to_vec).collect();
+    read_pixels(&rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    const TEST_INPUT: &str = indoc! {"
+        .##..###..#..#.####.#...#
+        #..#.#..#.#..#.#....#...#
+        #..#.###..####.###...#.#.
+        ####.#..#.#..#.#......#..
+        #..#.#..#.#..#.#......#..
+        #..#.###..#..#.####...#..
+    "};
+
+    const TEST_INPUT2: &str = indoc! {"
+
+        #....#..######
+        #....#.......#
+        .#..#........#
+        .#..#.......#.
+        ..##.......#..
+        ..##......#...
+        .#..#....#....
+        .#..#...#.....
+        #....#..#.....
+        #....#..######
+
+    "};
+
+    #[test]
+    fn test_font_6() {
+        assert_eq!(read(TEST_INPUT), Ok("ABHEY".to_owned()));
+        let all: String = FONT_6.iter().map(|&(c, _)| c).collect();
+        let rows: Vec<String> = (0..6)
+            .map(|y| {
+                FONT_6
+                    .iter()
+                    .map(|(_, glyph)| glyph.split('|').nth(y).unwrap())
+                    .collect::<Vec<_>>()
+                    .join(".")
+            })
+            .collect();
+        assert_eq!(read(&rows.join("\n")), Ok(all));
+    }
+
+    #[test]
+    fn test_font_10() {
+        assert_eq!(read(TEST_INPUT2), Ok("XZ".to_owned()));
+    }
+
+    #[test]
+    fn test_unknown_glyph() {
+        let input = TEST_INPUT.replace(".##..###", ".##..#.#");
+        let Err(OcrError::UnknownGlyphs(text, glyphs)) = read(&input) else {
+            panic!("expected unknown glyphs");
+        };
+        assert_eq!(text, "A?HEY");
+        assert_eq!(glyphs.len(), 1);
+        assert_eq!(glyphs[0].index, 1);
+        assert_eq!(glyphs[0].rows[0], "#.#.");
+        assert!(read(&input).unwrap_err().to_string().contains("#.#."));
+    }
+
+    #[test]
+    fn test_height() {
+        assert_eq!(read("#\n#\n#"), Err(OcrError::Height(3)));
+    }
+
+    #[test]
+    fn test_read_grid() {
+        let mut grid = Grid::new(9, 8, false);
+        for (y, row) in ["####", "#...", "###.", "#...", "#...", "#..."]
+            .iter()
+            .enumerate()
+        {
+            for (x, c) in row.chars().enumerate() {
+                grid[(x + 2, y + 1)] = c == '#';
+            }
+        }
+        assert_eq!(read_grid(&grid), Ok("F".to_owned()));
+    }
+}
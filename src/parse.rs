@@ -0,0 +1,263 @@
+use std::fmt::Display;
+
+use nom::error::{ContextError, ErrorKind, FromExternalError};
+use nom_locate::LocatedSpan;
+
+/// Puzzle input that keeps track of its line and column while being parsed.
+pub type Span<'a> = LocatedSpan<&'a str>;
+
+/// Lines longer than this are cut down to the part around the error.
+const EXCERPT_WIDTH: usize = 60;
+/// How much of the unparsed rest [`ParseError::leftover`] quotes.
+const LEFTOVER_WIDTH: usize = 20;
+
+/// Where parsing stopped, what was expected there and what was being parsed.
+///
+/// Used as the error type of every nom parser in the crate, so [`nom::error::context`]
+/// labels end up in the message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based, 0 when parsing ran out of input.
+    pub line: u32,
+    /// 1-based, counted in characters.
+    pub column: usize,
+    /// The line parsing stopped on, shortened to about [`EXCERPT_WIDTH`] characters.
+    pub excerpt: String,
+    pub expected: String,
+    /// Labels added with [`nom::error::context`], innermost first.
+    pub context: Vec<&'static str>,
+    /// Position of the column in `excerpt`, counted in characters from 0.
+    caret: usize,
+}
+
+/// Cuts `line` down to a window around `column`, marking the cuts with `...`.
+fn excerpt(line: &str, column: usize) -> (String, usize) {
+    let chars: Vec<char> = line.chars().collect();
+    let at = column.saturating_sub(1);
+    if chars.len() <= EXCERPT_WIDTH {
+        return (line.to_owned(), at);
+    }
+
+    let start = at
+        .saturating_sub(EXCERPT_WIDTH / 2)
+        .min(chars.len() - EXCERPT_WIDTH);
+    let end = start + EXCERPT_WIDTH;
+    let mut excerpt = String::new();
+    let mut caret = at - start;
+    if start > 0 {
+        excerpt.push_str("...");
+        caret += 3;
+    }
+    excerpt.extend(&chars[start..end]);
+    if end < chars.len() {
+        excerpt.push_str("...");
+    }
+    (excerpt, caret)
+}
+
+impl ParseError {
+    pub fn new(line: u32, column: usize, source_line: &str, expected: &str) -> Self {
+        let (excerpt, caret) = excerpt(source_line, column);
+        Self {
+            line,
+            column,
+            excerpt,
+            expected: expected.to_owned(),
+            context: Vec::new(),
+            caret,
+        }
+    }
+
+    /// The error at the start of `span`.
+    pub fn at(span: Span, expected: &str) -> Self {
+        Self::new(
+            span.location_line(),
+            span.get_utf8_column(),
+            &String::from_utf8_lossy(span.get_line_beginning()),
+            expected,
+        )
+    }
+
+    /// The error for input left over after parsing, quoting the start of it.
+    pub fn leftover(rest: Span) -> Self {
+        let rest_str = *rest.fragment();
+        let quoted = match rest_str.char_indices().nth(LEFTOVER_WIDTH) {
+            Some((i, _)) => format!("{:?}...", &rest_str[..i]),
+            None => format!("{:?}", rest_str),
+        };
+        Self::at(rest, &format!("the end of the input, found {quoted}"))
+    }
+}
+
+impl From<nom::Err<ParseError>> for ParseError {
+    fn from(e: nom::Err<ParseError>) -> Self {
+        match e {
+            nom::Err::Incomplete(_) => Self::new(0, 0, "", "more input"),
+            nom::Err::Error(e) | nom::Err::Failure(e) => e,
+        }
+    }
+}
+
+impl<'a> nom::error::ParseError<Span<'a>> for ParseError {
+    fn from_error_kind(input: Span<'a>, kind: ErrorKind) -> Self {
+        Self::at(input, expected(&kind))
+    }
+
+    fn append(_: Span<'a>, _: ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    fn from_char(input: Span<'a>, c: char) -> Self {
+        Self::at(input, &format!("{c:?}"))
+    }
+
+    /// Alternatives failing at the same place are all listed as expected.
+    fn or(self, other: Self) -> Self {
+        if (self.line, self.column) == (other.line, other.column) && self.expected != other.expected
+        {
+            Self {
+                expected: format!("{} or {}", self.expected, other.expected),
+                ..other
+            }
+        } else {
+            other
+        }
+    }
+}
+
+impl<'a> ContextError<Span<'a>> for ParseError {
+    fn add_context(_: Span<'a>, context: &'static str, mut other: Self) -> Self {
+        other.context.push(context);
+        other
+    }
+}
+
+impl<'a, E: Display> FromExternalError<Span<'a>, E> for ParseError {
+    fn from_external_error(input: Span<'a>, _: ErrorKind, e: E) -> Self {
+        Self::at(input, &e.to_string())
+    }
+}
+
+/// Describes the construct a failing nom parser of `kind` was looking for.
+fn expected(kind: &ErrorKind) -> &str {
+    match kind {
+        ErrorKind::Digit => "a number",
+        ErrorKind::Char => "a specific character",
+        ErrorKind::Tag => "a specific word",
+        ErrorKind::Alpha => "a letter",
+        ErrorKind::AlphaNumeric => "a letter or digit",
+        ErrorKind::Space | ErrorKind::MultiSpace => "whitespace",
+        ErrorKind::CrLf => "a line break",
+        ErrorKind::Eof => "the end of the input",
+        ErrorKind::Complete => "more input",
+        ErrorKind::SeparatedList | ErrorKind::Many1 => "at least one item",
+        ErrorKind::Alt | ErrorKind::Permutation => "one of several alternatives",
+        kind => kind.description(),
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.line == 0 {
+            return write!(f, "unexpected end of input, expected {}", self.expected);
+        }
+        write!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )?;
+        for context in &self.context {
+            write!(f, ", in {}", context)?;
+        }
+        write!(f, "\n{}\n{:>2$}^", self.excerpt, "", self.caret)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IResult;
+    use indoc::indoc;
+    use nom::{
+        character::complete::{self, newline},
+        error::context,
+        multi::separated_list1,
+        sequence::separated_pair,
+        Parser,
+    };
+
+    const TEST_INPUT: &str = indoc! {"
+        3-5
+        10-14
+        16-x0
+    "};
+
+    fn ranges(input: Span) -> IResult<Span, Vec<(u64, u64)>> {
+        context(
+            "range list",
+            separated_list1(
+                newline,
+                separated_pair(
+                    context("range start", complete::u64),
+                    complete::char('-'),
+                    context("range end", complete::u64),
+                ),
+            ),
+        )
+        .parse(input)
+    }
+
+    #[test]
+    fn test_position() {
+        let (rest, parsed) = ranges(Span::new(TEST_INPUT)).unwrap();
+        assert_eq!(parsed.len(), 2);
+
+        let e = ParseError::at(rest, "a range");
+        assert_eq!((e.line, e.column), (2, 6));
+        assert_eq!(e.excerpt, "10-14");
+    }
+
+    #[test]
+    fn test_display() {
+        let e = ParseError::from(ranges(Span::new("16-x0")).unwrap_err());
+        assert_eq!(e.context, ["range end", "range list"]);
+        assert_eq!(
+            e.to_string(),
+            "line 1, column 4: expected a number, in range end, in range list\n16-x0\n   ^"
+        );
+
+        let e = ParseError::from(ranges(Span::new("16+20")).unwrap_err());
+        assert!(e.to_string().starts_with("line 1, column 3: expected '-'"));
+    }
+
+    #[test]
+    fn test_leftover() {
+        // `ranges` stops before the bad third line, which is then left over
+        let e = crate::parse_all(ranges, TEST_INPUT.trim_end()).unwrap_err();
+        assert_eq!((e.line, e.column), (2, 6));
+        assert_eq!(e.expected, r#"the end of the input, found "\n16-x0""#);
+        assert!(crate::parse_all(ranges, "3-5\n10-14\n").is_ok());
+
+        let e = crate::parse_all(ranges, "3-5\n10-14\n\n").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 6));
+        assert_eq!(e.expected, r#"the end of the input, found "\n\n""#);
+
+        let e = crate::parse_all(ranges, &format!("3-5 {}", "x".repeat(30))).unwrap_err();
+        assert!(e.expected.ends_with(r#"found " xxxxxxxxxxxxxxxxxxx"..."#));
+    }
+
+    #[test]
+    fn test_excerpt() {
+        let line = format!("{}x{}", "1-2,".repeat(50), "3-4,".repeat(50));
+        let e = ParseError::new(1, 201, &line, "a number");
+        assert_eq!(e.excerpt.chars().count(), EXCERPT_WIDTH + 6);
+        assert!(e.excerpt.starts_with("...") && e.excerpt.ends_with("..."));
+        assert_eq!(e.excerpt.chars().nth(e.caret), Some('x'));
+
+        let e = ParseError::new(1, 2, &line, "a number");
+        assert!(e.excerpt.starts_with("1-2,") && e.excerpt.ends_with("..."));
+        assert_eq!(e.caret, 1);
+    }
+}
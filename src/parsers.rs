@@ -0,0 +1,211 @@
+//! Parsers for the shapes puzzle input keeps coming in, to be combined with nom and run
+//! with [`crate::parse_all`].
+
+use std::ops::RangeInclusive;
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{self, alpha1, newline, space1},
+    error::context,
+    multi::separated_list1,
+    sequence::separated_pair,
+    Parser,
+};
+
+use crate::{
+    parse::{ParseError, Span},
+    IResult,
+};
+
+/// An unsigned number like `42`.
+pub fn number(input: Span) -> IResult<Span, usize> {
+    context("number", complete::usize).parse(input)
+}
+
+/// A number with an optional sign like `-7` or `+3`.
+pub fn signed(input: Span) -> IResult<Span, i64> {
+    context("signed number", complete::i64).parse(input)
+}
+
+/// A run of letters like `svr`, borrowed from the input.
+pub fn word<'a>(input: Span<'a>) -> IResult<Span<'a>, &'a str> {
+    context("word", alpha1)
+        .map(|word: Span<'a>| *word.fragment())
+        .parse(input)
+}
+
+/// An inclusive range like `10-14`.
+pub fn range(input: Span) -> IResult<Span, RangeInclusive<usize>> {
+    separated_pair(
+        context("range start", complete::usize),
+        complete::char('-'),
+        context("range end", complete::usize),
+    )
+    .map(|(start, end)| start..=end)
+    .parse(input)
+}
+
+/// `item`s separated by commas like `1,2,3`, at least one.
+pub fn comma_list<'a, P>(
+    item: P,
+) -> impl Parser<Span<'a>, Output = Vec<P::Output>, Error = ParseError>
+where
+    P: Parser<Span<'a>, Error = ParseError>,
+{
+    separated_list1(complete::char(','), item)
+}
+
+/// `item`s separated by spaces like `aaa bbb`, at least one.
+pub fn space_list<'a, P>(
+    item: P,
+) -> impl Parser<Span<'a>, Output = Vec<P::Output>, Error = ParseError>
+where
+    P: Parser<Span<'a>, Error = ParseError>,
+{
+    separated_list1(space1, item)
+}
+
+/// One `item` per line, at least one. A blank line ends the list.
+pub fn lines<'a, P>(item: P) -> impl Parser<Span<'a>, Output = Vec<P::Output>, Error = ParseError>
+where
+    P: Parser<Span<'a>, Error = ParseError>,
+{
+    separated_list1(newline, item)
+}
+
+/// Blocks of input separated by blank lines, each parsed by `section`.
+pub fn sections<'a, P>(
+    section: P,
+) -> impl Parser<Span<'a>, Output = Vec<P::Output>, Error = ParseError>
+where
+    P: Parser<Span<'a>, Error = ParseError>,
+{
+    separated_list1((newline, newline), section)
+}
+
+/// Two differently shaped blocks of input separated by a blank line.
+pub fn section_pair<'a, F, S>(
+    first: F,
+    second: S,
+) -> impl Parser<Span<'a>, Output = (F::Output, S::Output), Error = ParseError>
+where
+    F: Parser<Span<'a>, Error = ParseError>,
+    S: Parser<Span<'a>, Error = ParseError>,
+{
+    separated_pair(first, (newline, newline), second)
+}
+
+/// Exactly `N` comma separated `number`s like `162,817,812`.
+pub fn coordinate<'a, const N: usize, P>(
+    mut number: P,
+) -> impl Parser<Span<'a>, Output = [P::Output; N], Error = ParseError>
+where
+    P: Parser<Span<'a>, Error = ParseError>,
+{
+    move |mut input: Span<'a>| {
+        let mut values = Vec::with_capacity(N);
+        for i in 0..N {
+            if i > 0 {
+                (input, _) = complete::char(',').parse(input)?;
+            }
+            let (rest, value) = number.parse(input)?;
+            input = rest;
+            values.push(value);
+        }
+        let Ok(values) = values.try_into() else {
+            unreachable!("exactly N values are parsed");
+        };
+        Ok((input, values))
+    }
+}
+
+/// A labelled line like `aaa: you hhh`, giving the label and the space separated values.
+pub fn labelled<'a, K, V>(
+    key: K,
+    value: V,
+) -> impl Parser<Span<'a>, Output = (K::Output, Vec<V::Output>), Error = ParseError>
+where
+    K: Parser<Span<'a>, Error = ParseError>,
+    V: Parser<Span<'a>, Error = ParseError>,
+{
+    separated_pair(key, tag(": "), space_list(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_all;
+    use indoc::indoc;
+
+    const TEST_INPUT: &str = indoc! {"
+        3-5
+        10-14
+
+        1
+        5
+    "};
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(parse_all(number, "42"), Ok(42));
+        assert_eq!(parse_all(signed, "-7"), Ok(-7));
+        assert_eq!(parse_all(signed, "+3"), Ok(3));
+        assert_eq!(parse_all(number, "-7").unwrap_err().context, ["number"]);
+        assert_eq!(parse_all(word, "svr"), Ok("svr"));
+    }
+
+    #[test]
+    fn test_lists() {
+        assert_eq!(parse_all(comma_list(number), "1,2,3"), Ok(vec![1, 2, 3]));
+        assert_eq!(
+            parse_all(space_list(word), "aaa bbb"),
+            Ok(vec!["aaa", "bbb"])
+        );
+        assert_eq!(parse_all(lines(signed), "1\n-2\n"), Ok(vec![1, -2]));
+        assert!(parse_all(comma_list(number), "1,2,").is_err());
+    }
+
+    #[test]
+    fn test_range() {
+        assert_eq!(parse_all(range, "10-14"), Ok(10..=14));
+        let e = parse_all(range, "10-x").unwrap_err();
+        assert_eq!(e.column, 4);
+        assert_eq!(e.context, ["range end"]);
+    }
+
+    #[test]
+    fn test_sections() {
+        assert_eq!(
+            parse_all(section_pair(lines(range), lines(number)), TEST_INPUT),
+            Ok((vec![3..=5, 10..=14], vec![1, 5]))
+        );
+        assert_eq!(
+            parse_all(sections(lines(number)), "1\n2\n\n3\n\n4"),
+            Ok(vec![vec![1, 2], vec![3], vec![4]])
+        );
+    }
+
+    #[test]
+    fn test_coordinate() {
+        assert_eq!(
+            parse_all(coordinate(number), "162,817,812"),
+            Ok([162, 817, 812])
+        );
+        assert_eq!(parse_all(coordinate(signed), "-1,2"), Ok([-1, 2]));
+        assert_eq!(
+            parse_all(lines(coordinate::<2, _>(number)), "7,1\n11,1"),
+            Ok(vec![[7, 1], [11, 1]])
+        );
+        let e = parse_all(coordinate::<3, _>(number), "1,2").unwrap_err();
+        assert_eq!(e.column, 4);
+        assert_eq!(e.expected, "','");
+    }
+
+    #[test]
+    fn test_labelled() {
+        assert_eq!(
+            parse_all(lines(labelled(word, word)), "aaa: you hhh\nyou: out"),
+            Ok(vec![("aaa", vec!["you", "hhh"]), ("you", vec!["out"])])
+        );
+    }
+}
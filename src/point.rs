@@ -0,0 +1,177 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::grid::Direction;
+
+/// A position or offset on a plane, `x` growing east and `y` growing south like grid
+/// indices do.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// Distance when diagonal steps count as one, like a king moving on a chess board.
+    pub fn chebyshev(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// The grid index of this point if it lies within `width` by `height`.
+    pub fn to_index(self, (width, height): (usize, usize)) -> Option<(usize, usize)> {
+        let x = usize::try_from(self.x).ok().filter(|&x| x < width)?;
+        let y = usize::try_from(self.y).ok().filter(|&y| y < height)?;
+        Some((x, y))
+    }
+
+    /// The grid index of this point if it lies within a square of `size`.
+    pub fn to_square_index(self, size: usize) -> Option<(usize, usize)> {
+        self.to_index((size, size))
+    }
+}
+
+impl From<(i64, i64)> for Point {
+    fn from((x, y): (i64, i64)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+/// Grid indices are far below `i64::MAX`, so the conversion does not overflow in practice.
+impl From<(usize, usize)> for Point {
+    fn from((x, y): (usize, usize)) -> Self {
+        Self::new(x as i64, y as i64)
+    }
+}
+
+impl From<Direction> for Point {
+    fn from(direction: Direction) -> Self {
+        direction.offset()
+    }
+}
+
+impl TryFrom<Point> for Direction {
+    type Error = Point;
+
+    /// The direction of a single step, failing for any other offset.
+    fn try_from(offset: Point) -> Result<Self, Self::Error> {
+        Direction::all()
+            .into_iter()
+            .find(|direction| direction.offset() == offset)
+            .ok_or(offset)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Point) -> Self::Output {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Add<Direction> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Direction) -> Self::Output {
+        self + rhs.offset()
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Point) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Point) -> Self::Output {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Point) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Point;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Point::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Self::Output {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Point::new(3, -2);
+        let b = Point::new(-1, 5);
+        assert_eq!(a + b, Point::new(2, 3));
+        assert_eq!(a - b, Point::new(4, -7));
+        assert_eq!(a * 3, Point::new(9, -6));
+        assert_eq!(-a, Point::new(-3, 2));
+        assert_eq!(a + Direction::East, Point::new(4, -2));
+
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Point::new(3, -2);
+        let b = Point::new(-1, 5);
+        assert_eq!(a.manhattan(b), 11);
+        assert_eq!(a.chebyshev(b), 7);
+        assert_eq!(a.manhattan(a), 0);
+    }
+
+    #[test]
+    fn test_direction_offsets() {
+        for direction in Direction::all() {
+            let offset = Point::from(direction);
+            assert_eq!(Direction::try_from(offset), Ok(direction));
+            assert_eq!(offset.chebyshev(Point::ORIGIN), 1);
+            assert_eq!(-offset, (!direction).offset());
+        }
+        assert_eq!(Direction::try_from(Point::new(2, 0)), Err(Point::new(2, 0)));
+        assert!(Direction::try_from(Point::ORIGIN).is_err());
+    }
+
+    #[test]
+    fn test_to_index() {
+        assert_eq!(Point::new(4, 1).to_index((5, 2)), Some((4, 1)));
+        assert_eq!(Point::new(5, 1).to_index((5, 2)), None);
+        assert_eq!(Point::new(4, 2).to_index((5, 2)), None);
+        assert_eq!(Point::new(-1, 0).to_index((5, 2)), None);
+        assert_eq!(Point::new(0, -1).to_index((5, 2)), None);
+        assert_eq!(Point::new(4, 4).to_square_index(5), Some((4, 4)));
+        assert_eq!(Point::new(4, 5).to_square_index(5), None);
+        assert_eq!(Point::from((4usize, 1usize)).to_index((5, 2)), Some((4, 1)));
+    }
+}
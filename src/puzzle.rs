@@ -0,0 +1,119 @@
+/// The example from one part of a puzzle description.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    pub answer: Option<String>,
+}
+
+/// Finds the first `open ... close` section of `text`, and the offset just past `close`.
+fn between<'a>(text: &'a str, open: &str, close: &str) -> Option<(&'a str, usize)> {
+    let start = text.find(open)? + open.len();
+    let end = start + text[start..].find(close)?;
+    Some((&text[start..end], end + close.len()))
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut rest = html;
+    while let Some(open) = rest.find('<') {
+        text.push_str(&rest[..open]);
+        match rest[open..].find('>') {
+            Some(close) => rest = &rest[open + close + 1..],
+            None => {
+                rest = "";
+                break;
+            }
+        }
+    }
+    text.push_str(rest);
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn articles(html: &str) -> Vec<&str> {
+    let mut articles = Vec::new();
+    let mut rest = html;
+    while let Some((article, end)) = between(rest, "<article", "</article>") {
+        articles.push(article);
+        rest = &rest[end..];
+    }
+    articles
+}
+
+/// Extracts the example of every part from a puzzle description page.
+///
+/// The example is the first `<pre><code>` block of a part, and the answer the last
+/// emphasized `<code><em>` value. A part without its own block reuses the previous example.
+pub fn examples(html: &str) -> Vec<Example> {
+    let mut examples: Vec<Example> = Vec::new();
+    for article in articles(html) {
+        let input = match between(article, "<pre><code>", "</code></pre>") {
+            Some((block, _)) => strip_tags(block).trim_end_matches('\n').to_owned(),
+            None => match examples.last() {
+                Some(previous) => previous.input.clone(),
+                None => continue,
+            },
+        };
+        let answer = article
+            .rmatch_indices("<code><em>")
+            .next()
+            .and_then(|(i, _)| between(&article[i..], "<code><em>", "</em></code>"))
+            .map(|(answer, _)| strip_tags(answer));
+        examples.push(Example { input, answer });
+    }
+    examples
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    const PUZZLE: &str = indoc! {r#"
+        <main>
+        <article class="day-desc"><h2>--- Day 5: Cafeteria ---</h2>
+        <p>For example:</p>
+        <pre><code>3-5
+        10-14
+
+        1
+        &lt;<em>5</em>&gt;
+        </code></pre>
+        <p>Ingredient ID <code>5</code> is <em>fresh</em>.</p>
+        <p>In this example, <code><em>3</em></code> of the available ingredient IDs are fresh.</p>
+        </article>
+        <p>Your puzzle answer was <code>744</code>.</p>
+        <article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+        <p>In this example, the fresh ingredient ID ranges consider a total of <code><em>14</em></code> ingredient IDs to be fresh.</p>
+        </article>
+        </main>
+    "#};
+
+    #[test]
+    fn test_examples() {
+        let examples = examples(PUZZLE);
+        let input = "3-5\n10-14\n\n1\n<5>";
+        assert_eq!(
+            examples,
+            vec![
+                Example {
+                    input: input.to_owned(),
+                    answer: Some("3".to_owned())
+                },
+                Example {
+                    input: input.to_owned(),
+                    answer: Some("14".to_owned())
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_articles() {
+        assert_eq!(examples("<html>Please log in</html>"), vec![]);
+    }
+}
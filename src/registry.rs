@@ -0,0 +1,75 @@
+use std::error::Error;
+
+use aoc_runner::{ArcStr, Runner};
+
+use crate::{solution::Solve, *};
+
+type Generate = fn(ArcStr) -> Result<Box<dyn Runner>, Box<dyn Error>>;
+
+/// The solutions of one year by day.
+pub type Solutions = &'static [(u8, &'static dyn Solve)];
+
+/// A part registered with `#[aoc(dayNN, partN)]`, together with its generator.
+pub struct Solver {
+    pub day: u8,
+    pub part: u8,
+    generate: Generate,
+}
+
+impl Solver {
+    const fn new(day: u8, part: u8, generate: Generate) -> Self {
+        Self {
+            day,
+            part,
+            generate,
+        }
+    }
+
+    /// Runs the generator for this part, returning a runner ready to solve it.
+    ///
+    /// `input` is trimmed the same way as for [`Solve::solve`].
+    pub fn generate(&self, input: &str) -> Result<Box<dyn Runner>, Box<dyn Error>> {
+        (self.generate)(ArcStr::from(input))
+    }
+}
+
+/// Every solver of the year declared in `aoc_lib!`, ordered by day and part.
+pub const SOLVERS: &[Solver] = &[
+    Solver::new(1, 1, Factory::day1_part1),
+    Solver::new(1, 2, Factory::day1_part2),
+    Solver::new(2, 1, Factory::day2_part1),
+    Solver::new(2, 2, Factory::day2_part2),
+    Solver::new(3, 1, Factory::day3_part1),
+    Solver::new(3, 2, Factory::day3_part2),
+    Solver::new(4, 1, Factory::day4_part1),
+    Solver::new(4, 2, Factory::day4_part2),
+    Solver::new(5, 1, Factory::day5_part1),
+    Solver::new(5, 2, Factory::day5_part2),
+    Solver::new(6, 1, Factory::day6_part1),
+    Solver::new(6, 2, Factory::day6_part2),
+    Solver::new(7, 1, Factory::day7_part1),
+    Solver::new(7, 2, Factory::day7_part2),
+    Solver::new(8, 1, Factory::day8_part1),
+    Solver::new(8, 2, Factory::day8_part2),
+    Solver::new(9, 1, Factory::day9_part1),
+    Solver::new(9, 2, Factory::day9_part2),
+    Solver::new(10, 1, Factory::day10_part1),
+    Solver::new(10, 2, Factory::day10_part2),
+    Solver::new(11, 1, Factory::day11_part1),
+    Solver::new(11, 2, Factory::day11_part2),
+];
+
+/// Every day implementing [`Solution`](crate::solution::Solution), ordered by day.
+pub const SOLUTIONS: Solutions = &[
+    (1, &day01::Day01),
+    (2, &day02::Day02),
+    (3, &day03::Day03),
+    (4, &day04::Day04),
+    (5, &day05::Day05),
+    (6, &day06::Day06),
+    (7, &day07::Day07),
+    (8, &day08::Day08),
+    (9, &day09::Day09),
+    (10, &day10::Day10),
+    (11, &day11::Day11),
+];
@@ -0,0 +1,473 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    ops::RangeInclusive,
+    panic::{self, AssertUnwindSafe},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use aoc::{
+    registry::{Solver, SOLVERS},
+    solution::Answer,
+};
+use clap::ValueEnum;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+/// Days and parts to run, written `5`, `5:2`, `1..=5`, `1..6` or `1..=5:1`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selection {
+    days: RangeInclusive<u8>,
+    part: Option<u8>,
+}
+
+impl Selection {
+    fn matches(&self, solver: &Solver) -> bool {
+        self.days.contains(&solver.day) && self.part.is_none_or(|part| part == solver.part)
+    }
+}
+
+impl FromStr for Selection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (days, part) = match s.split_once(':') {
+            Some((days, part)) => (days, Some(part)),
+            None => (s, None),
+        };
+        let number = |n: &str| {
+            n.trim()
+                .parse::<u8>()
+                .map_err(|e| format!("invalid number {:?}: {}", n, e))
+        };
+
+        let days = if let Some((start, end)) = days.split_once("..=") {
+            number(start)?..=number(end)?
+        } else if let Some((start, end)) = days.split_once("..") {
+            let end = number(end)?;
+            if end == 0 {
+                return Err(format!("empty day range: {}", s));
+            }
+            number(start)?..=end - 1
+        } else {
+            let day = number(days)?;
+            day..=day
+        };
+        if days.is_empty() {
+            return Err(format!("empty day range: {}", s));
+        }
+
+        let part = part.map(number).transpose()?;
+        if part.is_some_and(|part| !(1..=2).contains(&part)) {
+            return Err(format!("part must be 1 or 2: {}", s));
+        }
+        Ok(Self { days, part })
+    }
+}
+
+/// The registered solvers matching any of the selections, or all of them without any.
+pub fn select(selections: &[Selection]) -> Vec<&'static Solver> {
+    SOLVERS
+        .iter()
+        .filter(|solver| selections.is_empty() || selections.iter().any(|s| s.matches(solver)))
+        .collect()
+}
+
+/// The result of running one part, with the generator and the part timed separately.
+pub struct Report {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Result<Answer, String>,
+    pub generator: Duration,
+    pub solver: Duration,
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.answer {
+            Ok(Answer::Grid(grid)) => {
+                writeln!(f, "Day {} - Part {}:\n{}", self.day, self.part, grid)?
+            }
+            Ok(answer) => writeln!(f, "Day {} - Part {}: {}", self.day, self.part, answer)?,
+            Err(e) => writeln!(f, "Day {} - Part {}: failed: {}", self.day, self.part, e)?,
+        }
+        write!(
+            f,
+            "\tgenerator: {:?}, solver: {:?}",
+            self.generator, self.solver
+        )
+    }
+}
+
+pub fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "panicked".to_owned(),
+        },
+    }
+}
+
+/// Runs the solver on `input`, turning errors and panics, like an unfinished `todo!()`,
+/// into a failed report.
+pub fn run(solver: &Solver, input: &str) -> Report {
+    let mut report = Report {
+        day: solver.day,
+        part: solver.part,
+        answer: Err(String::new()),
+        generator: Duration::ZERO,
+        solver: Duration::ZERO,
+    };
+
+    let start = Instant::now();
+    let runner = panic::catch_unwind(AssertUnwindSafe(|| solver.generate(input)));
+    report.generator = start.elapsed();
+    let runner = match runner {
+        Ok(Ok(runner)) => runner,
+        Ok(Err(e)) => {
+            report.answer = Err(e.to_string());
+            return report;
+        }
+        Err(payload) => {
+            report.answer = Err(panic_message(payload));
+            return report;
+        }
+    };
+
+    let start = Instant::now();
+    let answer = panic::catch_unwind(AssertUnwindSafe(|| runner.try_run()));
+    report.solver = start.elapsed();
+    report.answer = match answer {
+        Ok(Ok(answer)) => Ok(answer.to_string().parse().unwrap()),
+        Ok(Err(e)) => Err(e.to_string()),
+        Err(payload) => Err(panic_message(payload)),
+    };
+    report
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Status {
+    Ok,
+    Failed,
+    MissingInput,
+    TimedOut,
+}
+
+/// One part of a run as reported in JSON output. Times are in nanoseconds.
+#[derive(Debug, Serialize)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub status: Status,
+    pub answer: Option<String>,
+    pub error: Option<String>,
+    pub generator_ns: Option<u128>,
+    pub solve_ns: Option<u128>,
+    pub input_sha256: Option<String>,
+}
+
+impl Record {
+    /// A part that did not produce a report, because of a missing input or a timeout.
+    pub fn unfinished(solver: &Solver, status: Status) -> Self {
+        Self {
+            day: solver.day,
+            part: solver.part,
+            status,
+            answer: None,
+            error: None,
+            generator_ns: None,
+            solve_ns: None,
+            input_sha256: None,
+        }
+    }
+
+    /// Runs the solver on `input` and records the outcome.
+    pub fn new(solver: &Solver, input: &str) -> Self {
+        let report = run(solver, input);
+        let (status, answer, error) = match report.answer {
+            Ok(answer) => (Status::Ok, Some(answer.to_string()), None),
+            Err(e) => (Status::Failed, None, Some(e)),
+        };
+        Self {
+            day: report.day,
+            part: report.part,
+            status,
+            answer,
+            error,
+            generator_ns: Some(report.generator.as_nanos()),
+            solve_ns: Some(report.solver.as_nanos()),
+            input_sha256: Some(format!("{:x}", Sha256::digest(input))),
+        }
+    }
+}
+
+impl Display for Record {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let json = serde_json::to_string(self).map_err(|_| std::fmt::Error)?;
+        write!(f, "{}", json)
+    }
+}
+
+/// The selected parts of one day, with the day's input if it could be read.
+pub struct DayJob {
+    pub solvers: Vec<&'static Solver>,
+    pub input: Option<Arc<str>>,
+}
+
+/// How a part fared when executed as part of a [`DayJob`].
+pub enum Execution<T> {
+    Done(T),
+    MissingInput,
+    TimedOut(Duration),
+}
+
+/// Runs `task` on every part of the days, handing the executions to `each` in order.
+///
+/// Up to `jobs` days run concurrently, the parts of a day one after the other. Each part
+/// runs on its own thread so that once the day has used up `timeout`, its remaining parts
+/// are reported as timed out and the next day starts. A timed out part cannot be stopped,
+/// it keeps running in the background until it finishes or the process exits, so more than
+/// `jobs` parts can be busy at once.
+pub fn execute<T, F>(
+    days: Vec<DayJob>,
+    jobs: usize,
+    timeout: Option<Duration>,
+    task: F,
+    mut each: impl FnMut(&'static Solver, Execution<T>),
+) where
+    T: Send + 'static,
+    F: Fn(&'static Solver, &str) -> T + Send + Sync + 'static,
+{
+    let task = Arc::new(task);
+    let next = AtomicUsize::new(0);
+    let (results, received) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, days.len().max(1)) {
+            let results = results.clone();
+            let (days, next, task) = (&days, &next, &task);
+            scope.spawn(move || loop {
+                let day = next.fetch_add(1, Ordering::Relaxed);
+                let Some(job) = days.get(day) else {
+                    return;
+                };
+                let started = Instant::now();
+                for (index, &solver) in job.solvers.iter().enumerate() {
+                    let execution = match &job.input {
+                        Some(input) => execute_part(solver, input, task, timeout, started),
+                        None => Execution::MissingInput,
+                    };
+                    if results.send(((day, index), execution)).is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+        drop(results);
+
+        // Results arrive in any order, hold them back until all earlier parts are out.
+        let mut order = days
+            .iter()
+            .enumerate()
+            .flat_map(|(day, job)| (0..job.solvers.len()).map(move |index| (day, index)))
+            .peekable();
+        let mut pending = BTreeMap::new();
+        for (key, execution) in received {
+            pending.insert(key, execution);
+            while let Some(execution) = order.peek().and_then(|key| pending.remove(key)) {
+                let (day, index) = order.next().unwrap();
+                each(days[day].solvers[index], execution);
+            }
+        }
+    });
+}
+
+fn execute_part<T, F>(
+    solver: &'static Solver,
+    input: &Arc<str>,
+    task: &Arc<F>,
+    timeout: Option<Duration>,
+    started: Instant,
+) -> Execution<T>
+where
+    T: Send + 'static,
+    F: Fn(&'static Solver, &str) -> T + Send + Sync + 'static,
+{
+    let remaining = timeout.map(|limit| limit.saturating_sub(started.elapsed()));
+    if remaining.is_some_and(|remaining| remaining.is_zero()) {
+        return Execution::TimedOut(timeout.unwrap());
+    }
+
+    let (sender, receiver) = mpsc::channel();
+    let (input, task) = (input.clone(), task.clone());
+    let handle = thread::spawn(move || {
+        // The receiver is gone once the part timed out.
+        let _ = sender.send(task(solver, &input));
+    });
+    let received = match remaining {
+        Some(remaining) => receiver.recv_timeout(remaining),
+        None => receiver
+            .recv()
+            .map_err(|_| mpsc::RecvTimeoutError::Disconnected),
+    };
+    match received {
+        Ok(result) => Execution::Done(result),
+        Err(mpsc::RecvTimeoutError::Timeout) => Execution::TimedOut(timeout.unwrap()),
+        Err(mpsc::RecvTimeoutError::Disconnected) => match handle.join() {
+            Err(payload) => panic::resume_unwind(payload),
+            Ok(()) => unreachable!("the part finished without a result"),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    const TEST_INPUT: &str = indoc! {"
+        3-5
+        10-14
+        16-20
+        12-18
+
+        1
+        5
+        8
+        11
+        17
+        32
+    "};
+
+    fn selection(s: &str) -> Selection {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_selection() {
+        assert_eq!(
+            selection("7"),
+            Selection {
+                days: 7..=7,
+                part: None
+            }
+        );
+        assert_eq!(
+            selection("7:2"),
+            Selection {
+                days: 7..=7,
+                part: Some(2)
+            }
+        );
+        assert_eq!(selection("1..=5").days, 1..=5);
+        assert_eq!(selection("1..6").days, 1..=5);
+        assert_eq!(selection("1..=5:1").part, Some(1));
+        assert!("5..=1".parse::<Selection>().is_err());
+        assert!("1..1".parse::<Selection>().is_err());
+        assert!("1:3".parse::<Selection>().is_err());
+        assert!("one".parse::<Selection>().is_err());
+    }
+
+    #[test]
+    fn test_select() {
+        assert_eq!(select(&[]).len(), SOLVERS.len());
+        let parts: Vec<_> = select(&[selection("2..=3"), selection("7:2")])
+            .iter()
+            .map(|solver| (solver.day, solver.part))
+            .collect();
+        assert_eq!(parts, vec![(2, 1), (2, 2), (3, 1), (3, 2), (7, 2)]);
+    }
+
+    #[test]
+    fn test_run() {
+        let solvers = select(&[selection("5")]);
+        let answers: Vec<_> = solvers
+            .iter()
+            .map(|solver| run(solver, TEST_INPUT).answer)
+            .collect();
+        assert_eq!(
+            answers,
+            vec![Ok(Answer::Integer(3)), Ok(Answer::Integer(14))]
+        );
+        assert!(run(solvers[0], "not ranges").answer.is_err());
+    }
+
+    #[test]
+    fn test_execute_in_order() {
+        let jobs = |days: &str| {
+            let solvers = select(&[selection(days)]);
+            solvers
+                .chunk_by(|a, b| a.day == b.day)
+                .map(|solvers| DayJob {
+                    solvers: solvers.to_vec(),
+                    input: (solvers[0].day != 3).then(|| Arc::from("")),
+                })
+                .collect::<Vec<_>>()
+        };
+        // Day 3 is done long before day 1, and day 2 takes longer than the timeout.
+        let task = |solver: &Solver, _: &str| {
+            let millis = match solver.day {
+                2 => 500,
+                day => 40 - day as u64 * 10,
+            };
+            thread::sleep(Duration::from_millis(millis));
+            (solver.day, solver.part)
+        };
+
+        let mut seen = Vec::new();
+        let timeout = Some(Duration::from_millis(200));
+        execute(jobs("1..=3"), 4, timeout, task, |solver, execution| {
+            let outcome = match execution {
+                Execution::Done(done) => format!("{:?}", done),
+                Execution::MissingInput => "missing".to_owned(),
+                Execution::TimedOut(_) => "timed out".to_owned(),
+            };
+            seen.push(((solver.day, solver.part), outcome));
+        });
+        assert_eq!(
+            seen,
+            vec![
+                ((1, 1), "(1, 1)".to_owned()),
+                ((1, 2), "(1, 2)".to_owned()),
+                ((2, 1), "timed out".to_owned()),
+                ((2, 2), "timed out".to_owned()),
+                ((3, 1), "missing".to_owned()),
+                ((3, 2), "missing".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json_record() {
+        let solvers = select(&[selection("6")]);
+        let input = "123 328\n 45 64 \n  6 98 \n*   +  ";
+        let records: Vec<_> = solvers
+            .iter()
+            .map(|solver| Record::new(solver, input))
+            .collect();
+        assert!(records.iter().all(|record| record.status == Status::Ok));
+        let json: serde_json::Value = serde_json::from_str(&records[1].to_string()).unwrap();
+        assert_eq!(json["day"], 6);
+        assert_eq!(json["part"], 2);
+        assert_eq!(json["status"], "ok");
+        assert_eq!(json["input_sha256"].as_str().unwrap().len(), 64);
+        assert!(json["solve_ns"].is_u64());
+
+        let missing = Record::unfinished(solvers[0], Status::MissingInput).to_string();
+        assert!(missing.contains(r#""status":"missing-input","answer":null"#));
+    }
+}
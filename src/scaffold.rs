@@ -0,0 +1,753 @@
+use std::{fmt::Display, path::PathBuf};
+
+use askama::Template;
+use clap::ValueEnum;
+
+use crate::puzzle::Example;
+
+/// The shape of a puzzle input, selecting which template a new day starts from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Kind {
+    /// One record per line, parsed with nom.
+    Lines,
+    /// A character grid of positions.
+    Grid,
+    /// `name: a b c` lines forming a directed graph.
+    Graph,
+    /// Blank line separated sections.
+    Sections,
+}
+
+impl Kind {
+    fn example(&self) -> &'static str {
+        match self {
+            Kind::Lines => "1\n2\n3",
+            Kind::Grid => "..#\n.#.\n#..",
+            Kind::Graph => "aaa: bbb ccc\nbbb: ccc",
+            Kind::Sections => "1-3\n5-8\n\n2\n7",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct DayInfo {
+    day: u8,
+    aoc_runner: bool,
+    /// Examples per part, there is always one for part 1.
+    examples: Vec<Example>,
+}
+
+fn indent_example(example: &str) -> String {
+    example
+        .lines()
+        .map(|line| match line {
+            "" => String::new(),
+            line => format!(
+                "        {}",
+                line.replace('\\', "\\\\").replace('"', "\\\"")
+            ),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn is_integer_answer(answer: &str) -> bool {
+    answer.parse::<usize>().is_ok()
+}
+
+fn answer_literal(answer: &str) -> String {
+    if is_integer_answer(answer) {
+        answer.to_owned()
+    } else {
+        format!("{:?}", answer)
+    }
+}
+
+/// What to call on a part's result before comparing it with `answer`. Parts start out
+/// returning `usize`, so any other answer is compared as a string.
+fn answer_conversion(answer: Option<&str>) -> &'static str {
+    match answer {
+        Some(answer) if !is_integer_answer(answer) => ".to_string()",
+        _ => "",
+    }
+}
+
+impl DayInfo {
+    pub fn new(day: u8, kind: Kind) -> Self {
+        Self {
+            day,
+            aoc_runner: true,
+            examples: vec![Example {
+                input: kind.example().to_owned(),
+                answer: None,
+            }],
+        }
+    }
+
+    /// Replaces the placeholder example with the ones from the puzzle description.
+    pub fn with_examples(mut self, examples: Vec<Example>) -> Self {
+        if !examples.is_empty() {
+            self.examples = examples;
+        }
+        self
+    }
+
+    /// Leaves out the aoc-runner attributes, making the solution functions public instead.
+    pub fn without_aoc_runner(mut self) -> Self {
+        self.aoc_runner = false;
+        self
+    }
+
+    fn module(&self) -> String {
+        format!("day{:02}", self.day)
+    }
+
+    fn type_name(&self) -> String {
+        format!("Day{:02}", self.day)
+    }
+
+    fn visibility(&self) -> &'static str {
+        if self.aoc_runner {
+            ""
+        } else {
+            "pub "
+        }
+    }
+
+    /// The example escaped for a string literal and indented to sit inside `indoc!`.
+    fn indented_example(&self) -> String {
+        indent_example(&self.examples[0].input)
+    }
+
+    fn expected_p1(&self) -> String {
+        match &self.examples[0].answer {
+            Some(answer) => answer_literal(answer),
+            None => "0".to_owned(),
+        }
+    }
+
+    /// The part 2 example, when it differs from the one for part 1.
+    fn indented_example_p2(&self) -> Option<String> {
+        let example = self.examples.get(1)?;
+        (example.input != self.examples[0].input).then(|| indent_example(&example.input))
+    }
+
+    fn input_name_p2(&self) -> &'static str {
+        match self.indented_example_p2() {
+            Some(_) => "TEST_INPUT2",
+            None => "TEST_INPUT",
+        }
+    }
+
+    fn expected_p2(&self) -> Option<String> {
+        self.examples.get(1)?.answer.as_deref().map(answer_literal)
+    }
+
+    fn conversion_p1(&self) -> &'static str {
+        answer_conversion(self.examples[0].answer.as_deref())
+    }
+
+    fn conversion_p2(&self) -> &'static str {
+        answer_conversion(self.examples.get(1).and_then(|e| e.answer.as_deref()))
+    }
+}
+
+#[derive(Template)]
+#[template(escape = "none")]
+enum DayTemplate<'a> {
+    #[template(path = "dayXX_lines.rs.askama")]
+    Lines { day: &'a DayInfo },
+    #[template(path = "dayXX_grid.rs.askama")]
+    Grid { day: &'a DayInfo },
+    #[template(path = "dayXX_graph.rs.askama")]
+    Graph { day: &'a DayInfo },
+    #[template(path = "dayXX_sections.rs.askama")]
+    Sections { day: &'a DayInfo },
+}
+
+/// Renders the starting `dayNN.rs` for a puzzle of the given shape.
+pub fn render_day(kind: Kind, day: &DayInfo) -> String {
+    let template = match kind {
+        Kind::Lines => DayTemplate::Lines { day },
+        Kind::Grid => DayTemplate::Grid { day },
+        Kind::Graph => DayTemplate::Graph { day },
+        Kind::Sections => DayTemplate::Sections { day },
+    };
+    // askama strips the final newline of the template
+    template.render().unwrap() + "\n"
+}
+
+#[derive(Template)]
+#[template(path = "yXXXX.rs.askama", escape = "none")]
+struct YearTemplate {
+    year: u32,
+}
+
+/// Renders the starting `yYYYY.rs` for a year outside of `aoc_lib!`, with an empty
+/// solution table for its days to be registered in.
+pub fn render_year(year: u32) -> String {
+    YearTemplate { year }.render().unwrap() + "\n"
+}
+
+/// Where the solution for a day lives and how it is wired into the crate.
+///
+/// Days of the year `aoc_lib!` is declared for sit directly in `src/` and use the aoc-runner
+/// attributes. Other years go in `src/yYYYY/` as public modules with plain functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layout {
+    year: u32,
+    day: u8,
+    nested: bool,
+}
+
+impl Layout {
+    pub fn new(year: u32, day: u8, runner_year: u32) -> Self {
+        Self {
+            year,
+            day,
+            nested: year != runner_year,
+        }
+    }
+
+    pub fn uses_aoc_runner(&self) -> bool {
+        !self.nested
+    }
+
+    fn year_module(&self) -> String {
+        format!("y{}", self.year)
+    }
+
+    pub fn day_path(&self) -> PathBuf {
+        let file = format!("day{:02}.rs", self.day);
+        if self.nested {
+            PathBuf::from("src").join(self.year_module()).join(file)
+        } else {
+            PathBuf::from("src").join(file)
+        }
+    }
+
+    /// The module for the year, for days outside of `aoc_lib!`.
+    pub fn year_path(&self) -> Option<PathBuf> {
+        self.nested
+            .then(|| PathBuf::from("src").join(format!("{}.rs", self.year_module())))
+    }
+
+    /// The declarations needed to reach the day, innermost first.
+    ///
+    /// Days using aoc-runner also get their part 1 added to the solver registry, and every
+    /// day gets its [`Solution`](aoc::solution::Solution) added to the solution table of its
+    /// year.
+    pub fn registrations(&self) -> Vec<(PathBuf, Declaration)> {
+        let src = PathBuf::from("src");
+        let day = self.day as u32;
+        let solution = format!(", &day{:02}::Day{:02}),", self.day, self.day);
+        if self.nested {
+            let year_path = src.join(format!("{}.rs", self.year_module()));
+            let year_solutions = format!(", {}::SOLUTIONS),", self.year_module());
+            vec![
+                (
+                    year_path.clone(),
+                    Declaration::module("pub mod day", day, 2),
+                ),
+                (
+                    year_path,
+                    Declaration::new("(", day, 0, &solution).after("pub const SOLUTIONS"),
+                ),
+                (
+                    src.join("lib.rs"),
+                    Declaration::module("pub mod y", self.year, 4),
+                ),
+                (
+                    src.join("lib.rs"),
+                    Declaration::new("(", self.year, 4, &year_solutions).after("const YEARS"),
+                ),
+            ]
+        } else {
+            let solver = format!(", 1, Factory::day{}_part1),", self.day);
+            vec![
+                (
+                    src.join("lib.rs"),
+                    Declaration::module("pub mod day", day, 2),
+                ),
+                (
+                    src.join("registry.rs"),
+                    Declaration::new("Solver::new(", day, 0, &solver).after("pub const SOLVERS"),
+                ),
+                (
+                    src.join("registry.rs"),
+                    Declaration::new("(", day, 0, &solution).after("pub const SOLUTIONS"),
+                ),
+            ]
+        }
+    }
+}
+
+/// A numbered declaration such as `mod day05;`, `pub mod y2024;` or a registry entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Declaration {
+    prefix: &'static str,
+    number: u32,
+    width: usize,
+    rest: String,
+    /// The line opening the list the declaration belongs in, for the first of its family.
+    anchor: Option<&'static str>,
+}
+
+impl Declaration {
+    /// A `{prefix}{number}{rest}` line, with the number zero padded to `width` digits.
+    pub fn new(prefix: &'static str, number: u32, width: usize, rest: &str) -> Self {
+        Self {
+            prefix,
+            number,
+            width,
+            rest: rest.to_owned(),
+            anchor: None,
+        }
+    }
+
+    /// Places the first declaration of its family right after the line starting with
+    /// `anchor`, such as the opening of a `const` table.
+    pub fn after(mut self, anchor: &'static str) -> Self {
+        self.anchor = Some(anchor);
+        self
+    }
+
+    /// A module declaration, `{prefix}{number};`.
+    pub fn module(prefix: &'static str, number: u32, width: usize) -> Self {
+        Self::new(prefix, number, width, ";")
+    }
+
+    pub fn line(&self) -> String {
+        format!(
+            "{}{:03$}{}",
+            self.prefix, self.number, self.rest, self.width
+        )
+    }
+
+    /// The number of a declaration from the same family as `self`.
+    fn sibling(&self, line: &str) -> Option<u32> {
+        let rest = line.trim().strip_prefix(self.prefix)?;
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        rest[..digits].parse().ok()
+    }
+}
+
+fn indentation(line: &str) -> &str {
+    &line[..line.len() - line.trim_start().len()]
+}
+
+fn is_module_declaration(line: &str) -> bool {
+    let line = line.trim();
+    (line.starts_with("mod ") || line.starts_with("pub mod ")) && line.ends_with(';')
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RegisterError {
+    AlreadyRegistered(String),
+    /// The declaration has no siblings and the line it goes after is missing.
+    MissingAnchor(&'static str),
+}
+
+impl Display for RegisterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RegisterError::AlreadyRegistered(declaration) => {
+                write!(f, "{declaration} is already declared")
+            }
+            RegisterError::MissingAnchor(anchor) => write!(f, "there is no `{anchor}`"),
+        }
+    }
+}
+
+/// The result of adding a module declaration to a source file.
+#[derive(Debug)]
+pub struct ModuleInsertion {
+    pub source: String,
+    /// Zero based line number of the inserted declaration in `source`.
+    pub line: usize,
+}
+
+/// Inserts the declaration into the block of its siblings, keeping it sorted.
+///
+/// Without any siblings it goes after its anchor, indented one level deeper, or else after
+/// the last module declaration, or at the end of the file.
+pub fn register_module(
+    source: &str,
+    declaration: &Declaration,
+) -> Result<ModuleInsertion, RegisterError> {
+    let mut lines: Vec<&str> = source.lines().collect();
+    let modules = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| Some((i, declaration.sibling(line)?)))
+        .collect::<Vec<_>>();
+
+    if modules.iter().any(|&(_, n)| n == declaration.number) {
+        return Err(RegisterError::AlreadyRegistered(declaration.line()));
+    }
+
+    let (line, indent) = match (modules.first(), declaration.anchor) {
+        (Some(&(first, _)), _) => {
+            let line = match modules.iter().find(|&&(_, n)| n > declaration.number) {
+                Some(&(i, _)) => i,
+                None => modules[modules.len() - 1].0 + 1,
+            };
+            (line, indentation(lines[first]).to_owned())
+        }
+        (None, Some(anchor)) => {
+            let i = lines
+                .iter()
+                .position(|line| line.trim_start().starts_with(anchor))
+                .ok_or(RegisterError::MissingAnchor(anchor))?;
+            (i + 1, format!("{}    ", indentation(lines[i])))
+        }
+        (None, None) => {
+            let line = lines
+                .iter()
+                .rposition(|line| is_module_declaration(line))
+                .map_or(lines.len(), |i| i + 1);
+            (line, String::new())
+        }
+    };
+    let new_line = format!("{}{}", indent, declaration.line());
+
+    lines.insert(line, &new_line);
+
+    let mut source = lines.join("\n");
+    source.push('\n');
+    Ok(ModuleInsertion { source, line })
+}
+
+impl ModuleInsertion {
+    /// Renders the insertion as a unified diff against `path`.
+    pub fn diff(&self, path: &str) -> String {
+        const CONTEXT: usize = 3;
+        let lines: Vec<&str> = self.source.lines().collect();
+        let start = self.line.saturating_sub(CONTEXT);
+        let end = (self.line + CONTEXT + 1).min(lines.len());
+
+        let mut out = format!("--- {path}\n+++ {path}\n");
+        // an empty original starts at line 0 rather than 1
+        let old_start = if end - start > 1 { start + 1 } else { start };
+        out += &format!(
+            "@@ -{},{} +{},{} @@\n",
+            old_start,
+            end - start - 1,
+            start + 1,
+            end - start
+        );
+        for (i, line) in lines.iter().enumerate().take(end).skip(start) {
+            let marker = if i == self.line { '+' } else { ' ' };
+            // blank context lines are emitted without the trailing space
+            out += format!("{marker}{line}").trim_end();
+            out.push('\n');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::files::temp_path;
+    use indoc::indoc;
+    use std::path::Path;
+
+    const LIB_RS: &str = indoc! {"
+        use nom::{error::Error, Err as NomErr};
+
+        mod day01;
+        mod day02;
+        mod day04;
+
+        extern crate aoc_runner;
+    "};
+
+    #[test]
+    fn test_render_kinds() {
+        for kind in Kind::value_variants() {
+            let rendered = render_day(*kind, &DayInfo::new(7, *kind));
+            assert!(rendered.contains("#[aoc(day07, part1)]"));
+            assert!(rendered.contains("impl Solution for Day07 {"));
+            assert!(rendered.contains("fn test_p1()"));
+        }
+    }
+
+    #[test]
+    fn test_render_without_aoc_runner() {
+        for kind in Kind::value_variants() {
+            let rendered = render_day(*kind, &DayInfo::new(7, *kind).without_aoc_runner());
+            assert!(!rendered.contains("#[aoc"));
+            assert!(rendered.contains("\npub fn part1("));
+        }
+    }
+
+    #[test]
+    fn test_indented_example() {
+        let day = DayInfo::new(1, Kind::Sections);
+        assert_eq!(
+            day.indented_example(),
+            "        1-3\n        5-8\n\n        2\n        7"
+        );
+    }
+
+    #[test]
+    fn test_render_examples() {
+        let day = DayInfo::new(5, Kind::Sections).with_examples(vec![
+            Example {
+                input: "3-5\n\n\"5\"".to_owned(),
+                answer: Some("3".to_owned()),
+            },
+            Example {
+                input: "1-2\n\n1".to_owned(),
+                answer: Some("AB".to_owned()),
+            },
+        ]);
+        let rendered = render_day(Kind::Sections, &day);
+        assert!(rendered.contains("        3-5\n\n        \\\"5\\\"\n"));
+        assert!(rendered.contains("assert_eq!(part1(&input_generator(TEST_INPUT).unwrap()), 3);"));
+        assert!(rendered.contains("const TEST_INPUT2: &str"));
+        assert!(rendered.contains(
+            "//     assert_eq!(part2(&input_generator(TEST_INPUT2).unwrap()).to_string(), \"AB\");"
+        ));
+
+        let day = DayInfo::new(5, Kind::Lines).with_examples(vec![Example {
+            input: "1".to_owned(),
+            answer: Some("-5".to_owned()),
+        }]);
+        assert!(render_day(Kind::Lines, &day).contains(
+            "assert_eq!(part1(&input_generator(TEST_INPUT).unwrap()).to_string(), \"-5\");"
+        ));
+    }
+
+    #[test]
+    fn test_insert_sorted() {
+        let insertion = register_module(LIB_RS, &Declaration::module("mod day", 3, 2)).unwrap();
+        assert_eq!(insertion.line, 4);
+        assert!(insertion
+            .source
+            .contains("mod day02;\nmod day03;\nmod day04;\n"));
+    }
+
+    #[test]
+    fn test_insert_last() {
+        let insertion = register_module(LIB_RS, &Declaration::module("mod day", 12, 2)).unwrap();
+        assert_eq!(insertion.line, 5);
+        assert!(insertion.source.contains("mod day04;\nmod day12;\n\n"));
+        assert!(insertion.source.ends_with('\n'));
+    }
+
+    #[test]
+    fn test_refuse_duplicate() {
+        assert_eq!(
+            register_module(LIB_RS, &Declaration::module("mod day", 2, 2)).unwrap_err(),
+            RegisterError::AlreadyRegistered("mod day02;".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_insert_first_of_family() {
+        let insertion =
+            register_module(LIB_RS, &Declaration::module("pub mod y", 2024, 4)).unwrap();
+        assert_eq!(insertion.line, 5);
+        assert!(insertion.source.contains("mod day04;\npub mod y2024;\n\n"));
+
+        let insertion = register_module("", &Declaration::module("pub mod day", 7, 2)).unwrap();
+        assert_eq!(insertion.source, "pub mod day07;\n");
+    }
+
+    #[test]
+    fn test_insert_registry_entry() {
+        let registry = indoc! {"
+            pub const SOLVERS: &[Solver] = &[
+                Solver::new(9, 2, Factory::day9_part2),
+                Solver::new(10, 1, Factory::day10_part1),
+            ];
+        "};
+        let entry = Declaration::new("Solver::new(", 12, 0, ", 1, Factory::day12_part1),");
+        let insertion = register_module(registry, &entry).unwrap();
+        assert_eq!(insertion.line, 3);
+        assert!(insertion
+            .source
+            .contains("day10_part1),\n    Solver::new(12, 1, Factory::day12_part1),\n];"));
+
+        let entry = Declaration::new("Solver::new(", 10, 0, ", 1, Factory::day10_part1),");
+        assert!(register_module(registry, &entry).is_err());
+    }
+
+    #[test]
+    fn test_layout() {
+        let flat = Layout::new(2025, 3, 2025);
+        assert!(flat.uses_aoc_runner());
+        assert_eq!(flat.day_path(), PathBuf::from("src/day03.rs"));
+        assert_eq!(flat.year_path(), None);
+        assert_eq!(
+            flat.registrations(),
+            vec![
+                (
+                    PathBuf::from("src/lib.rs"),
+                    Declaration::module("pub mod day", 3, 2)
+                ),
+                (
+                    PathBuf::from("src/registry.rs"),
+                    Declaration::new("Solver::new(", 3, 0, ", 1, Factory::day3_part1),")
+                        .after("pub const SOLVERS")
+                ),
+                (
+                    PathBuf::from("src/registry.rs"),
+                    Declaration::new("(", 3, 0, ", &day03::Day03),").after("pub const SOLUTIONS")
+                ),
+            ]
+        );
+
+        let nested = Layout::new(2016, 3, 2025);
+        assert!(!nested.uses_aoc_runner());
+        assert_eq!(nested.day_path(), PathBuf::from("src/y2016/day03.rs"));
+        assert_eq!(nested.year_path(), Some(PathBuf::from("src/y2016.rs")));
+        assert_eq!(
+            nested.registrations(),
+            vec![
+                (
+                    PathBuf::from("src/y2016.rs"),
+                    Declaration::module("pub mod day", 3, 2)
+                ),
+                (
+                    PathBuf::from("src/y2016.rs"),
+                    Declaration::new("(", 3, 0, ", &day03::Day03),").after("pub const SOLUTIONS")
+                ),
+                (
+                    PathBuf::from("src/lib.rs"),
+                    Declaration::module("pub mod y", 2016, 4)
+                ),
+                (
+                    PathBuf::from("src/lib.rs"),
+                    Declaration::new("(", 2016, 4, ", y2016::SOLUTIONS),").after("const YEARS")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_insert_after_anchor() {
+        let year = render_year(2016);
+        let entry = Declaration::new("(", 3, 0, ", &day03::Day03),").after("pub const SOLUTIONS");
+        let insertion = register_module(&year, &entry).unwrap();
+        assert!(insertion
+            .source
+            .ends_with("= &[\n    (3, &day03::Day03),\n];\n"));
+
+        let entry = Declaration::new("(", 1, 0, ", &day01::Day01),").after("pub const SOLUTIONS");
+        let insertion = register_module(&insertion.source, &entry).unwrap();
+        assert!(insertion
+            .source
+            .ends_with("    (1, &day01::Day01),\n    (3, &day03::Day03),\n];\n"));
+
+        assert_eq!(
+            register_module(LIB_RS, &entry).unwrap_err(),
+            RegisterError::MissingAnchor("pub const SOLUTIONS")
+        );
+    }
+
+    /// Renders every kind of day into a copy of this crate, both in the `aoc_lib!` year and
+    /// in a nested one, registers them like `scaffold` does and checks that it compiles.
+    #[test]
+    fn test_rendered_days_compile() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let dir = temp_path("scaffold");
+        std::fs::create_dir_all(&dir).unwrap();
+        for entry in ["Cargo.toml", "Cargo.lock", "src", "templates"] {
+            if root.join(entry).exists() {
+                copy_recursively(&root.join(entry), &dir.join(entry));
+            }
+        }
+
+        // text answers have to compile against parts returning `usize` as well
+        let answers = ["AB", "6,0,1", "-5", "42"];
+        for (i, kind) in Kind::value_variants().iter().enumerate() {
+            let flat = Layout::new(aoc::YEAR, 21 + i as u8, aoc::YEAR);
+            let nested = Layout::new(2016, 1 + i as u8, aoc::YEAR);
+            for layout in [flat, nested] {
+                let examples = (0..2)
+                    .map(|part| Example {
+                        input: kind.example().to_owned(),
+                        answer: Some(answers[(i + part) % answers.len()].to_owned()),
+                    })
+                    .collect();
+                let mut day = DayInfo::new(layout.day, *kind).with_examples(examples);
+                if !layout.uses_aoc_runner() {
+                    day = day.without_aoc_runner();
+                }
+                let path = dir.join(layout.day_path());
+                std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+                std::fs::write(path, render_day(*kind, &day)).unwrap();
+
+                if let Some(year_path) = layout.year_path().map(|path| dir.join(path)) {
+                    if !year_path.exists() {
+                        std::fs::write(year_path, render_year(layout.year)).unwrap();
+                    }
+                }
+                for (file, declaration) in layout.registrations() {
+                    let file = dir.join(file);
+                    let source = std::fs::read_to_string(&file).unwrap();
+                    // the year module is declared by the first nested day already
+                    match register_module(&source, &declaration) {
+                        Ok(insertion) => std::fs::write(file, insertion.source).unwrap(),
+                        Err(RegisterError::AlreadyRegistered(_)) => {}
+                        Err(e) => panic!("{e}"),
+                    }
+                }
+            }
+        }
+
+        let output = std::process::Command::new(env!("CARGO"))
+            .args(["check", "--offline", "--lib", "--tests", "--quiet"])
+            .current_dir(&dir)
+            // shared between runs so the dependencies are only checked once
+            .env(
+                "CARGO_TARGET_DIR",
+                std::env::temp_dir().join("aoc-scaffold-target"),
+            )
+            .output()
+            .unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    fn copy_recursively(from: &Path, to: &Path) {
+        if from.is_dir() {
+            std::fs::create_dir_all(to).unwrap();
+            for entry in std::fs::read_dir(from).unwrap() {
+                let entry = entry.unwrap();
+                copy_recursively(&entry.path(), &to.join(entry.file_name()));
+            }
+        } else {
+            std::fs::copy(from, to).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_diff() {
+        let insertion = register_module(LIB_RS, &Declaration::module("mod day", 3, 2)).unwrap();
+        assert_eq!(
+            insertion.diff("src/lib.rs"),
+            indoc! {"
+                --- src/lib.rs
+                +++ src/lib.rs
+                @@ -2,6 +2,7 @@
+
+                 mod day01;
+                 mod day02;
+                +mod day03;
+                 mod day04;
+
+                 extern crate aoc_runner;
+            "}
+        );
+    }
+}
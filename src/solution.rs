@@ -0,0 +1,262 @@
+use std::{cmp::Ordering, convert::Infallible, error::Error, fmt::Display, str::FromStr};
+
+use num::{BigInt, BigUint};
+
+/// The answer to one part of a puzzle.
+///
+/// Integers compare equal regardless of whether they are stored as [`Answer::Integer`]
+/// or [`Answer::BigInteger`], and only numbers are ordered.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Integer(i128),
+    BigInteger(BigInt),
+    Text(String),
+    /// A picture, like letters drawn on a grid, as rows separated by newlines.
+    Grid(String),
+}
+
+impl Answer {
+    /// Renders `grid` into a grid answer, dropping blank lines around it.
+    pub fn grid(grid: impl Display) -> Self {
+        let rendered = grid.to_string();
+        let rows: Vec<_> = rendered
+            .lines()
+            .skip_while(|row| row.trim().is_empty())
+            .collect();
+        let end = rows
+            .iter()
+            .rposition(|row| !row.trim().is_empty())
+            .map_or(0, |i| i + 1);
+        Answer::Grid(rows[..end].join("\n"))
+    }
+
+    fn number(&self) -> Option<BigInt> {
+        match self {
+            Answer::Integer(n) => Some(BigInt::from(*n)),
+            Answer::BigInteger(n) => Some(n.clone()),
+            Answer::Text(_) | Answer::Grid(_) => None,
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(n) => write!(f, "{}", n),
+            Answer::BigInteger(n) => write!(f, "{}", n),
+            Answer::Text(s) | Answer::Grid(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.number(), other.number()) {
+            (Some(a), Some(b)) => a == b,
+            (None, None) => match (self, other) {
+                (Answer::Text(a), Answer::Text(b)) | (Answer::Grid(a), Answer::Grid(b)) => a == b,
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl PartialOrd for Answer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self.number(), other.number()) {
+            (Some(a), Some(b)) => Some(a.cmp(&b)),
+            _ => (self == other).then_some(Ordering::Equal),
+        }
+    }
+}
+
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    /// Reads back a displayed answer, picking the narrowest variant that fits.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim_matches('\n');
+        Ok(if s.contains('\n') {
+            Answer::Grid(s.to_owned())
+        } else if let Ok(n) = s.parse() {
+            Answer::Integer(n)
+        } else if let Ok(n) = s.parse() {
+            Answer::BigInteger(n)
+        } else {
+            Answer::Text(s.to_owned())
+        })
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Answer::Integer(n as i128)
+            }
+        })*
+    };
+}
+
+impl_from_integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, i128);
+
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        match i128::try_from(n) {
+            Ok(n) => Answer::Integer(n),
+            Err(_) => Answer::BigInteger(n.into()),
+        }
+    }
+}
+
+impl From<BigInt> for Answer {
+    fn from(n: BigInt) -> Self {
+        Answer::BigInteger(n)
+    }
+}
+
+impl From<BigUint> for Answer {
+    fn from(n: BigUint) -> Self {
+        Answer::BigInteger(n.into())
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_owned())
+    }
+}
+
+/// A day's puzzle: parse the input once, then solve either part from it.
+pub trait Solution {
+    type Input;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Box<dyn Error>>;
+    fn part1(&self, input: &Self::Input) -> Answer;
+    fn part2(&self, input: &Self::Input) -> Answer;
+}
+
+/// A [`Solution`] with its input type erased, so solutions of different days can be
+/// stored together, see [`crate::registry()`].
+pub trait Solve: Sync {
+    /// Parses `input` and solves `part`, 1 or 2.
+    ///
+    /// Trailing newlines are trimmed from `input` first, the same as `cargo aoc` does.
+    fn solve(&self, input: &str, part: u8) -> Result<Answer, Box<dyn Error>>;
+
+    /// Parses `input` once and solves both parts.
+    fn solve_both(&self, input: &str) -> Result<[Answer; 2], Box<dyn Error>>;
+}
+
+impl<S: Solution + Sync> Solve for S {
+    fn solve(&self, input: &str, part: u8) -> Result<Answer, Box<dyn Error>> {
+        let input = self.parse(input.trim_end_matches('\n'))?;
+        match part {
+            1 => Ok(self.part1(&input)),
+            2 => Ok(self.part2(&input)),
+            _ => Err(format!("there is no part {}", part).into()),
+        }
+    }
+
+    fn solve_both(&self, input: &str) -> Result<[Answer; 2], Box<dyn Error>> {
+        let input = self.parse(input.trim_end_matches('\n'))?;
+        Ok([self.part1(&input), self.part2(&input)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    const TEST_INPUT: &str = indoc! {"
+        3-5
+        10-14
+        16-20
+        12-18
+
+        1
+        5
+        8
+        11
+        17
+        32
+    "};
+
+    fn answer(s: &str) -> Answer {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(Answer::from(3usize), Answer::Integer(3));
+        assert_eq!(Answer::from(-3i64).to_string(), "-3");
+        assert_eq!(Answer::from("abc").to_string(), "abc");
+        assert_eq!(Answer::from(u128::MAX).to_string(), u128::MAX.to_string());
+    }
+
+    #[test]
+    fn test_parse_answer() {
+        assert!(matches!(answer("744"), Answer::Integer(744)));
+        assert!(matches!(answer("-5\n"), Answer::Integer(-5)));
+        let big = "123456789012345678901234567890123456789012";
+        assert!(matches!(answer(big), Answer::BigInteger(_)));
+        assert_eq!(answer(big).to_string(), big);
+        assert_eq!(answer("6,0,1"), Answer::Text("6,0,1".to_owned()));
+        assert_eq!(answer("#.\n.#"), Answer::Grid("#.\n.#".to_owned()));
+    }
+
+    #[test]
+    fn test_compare_answers() {
+        assert_eq!(Answer::BigInteger(BigInt::from(42)), Answer::Integer(42));
+        assert!(answer("99999999999999999999999999999999999999999") > Answer::Integer(7));
+        assert!(Answer::Integer(-1) < Answer::Integer(0));
+        assert_ne!(Answer::Text("42".to_owned()), Answer::Integer(42));
+        assert_eq!(answer("abc").partial_cmp(&answer("abd")), None);
+        assert_eq!(
+            answer("abc").partial_cmp(&answer("abc")),
+            Some(Ordering::Equal)
+        );
+    }
+
+    #[test]
+    fn test_grid_answer() {
+        let rendered = "\n#..#\n####\n\n";
+        let grid = Answer::grid(rendered);
+        assert_eq!(grid, Answer::Grid("#..#\n####".to_owned()));
+        assert_eq!(grid.to_string(), "#..#\n####");
+        assert_eq!(answer(&grid.to_string()), grid);
+    }
+
+    #[test]
+    fn test_registry() {
+        let registry = crate::registry();
+        assert_eq!(registry.len(), 11);
+
+        let day05 = registry[&(2025, 5)];
+        assert_eq!(day05.solve(TEST_INPUT, 1).unwrap(), Answer::Integer(3));
+        assert_eq!(
+            day05.solve_both(TEST_INPUT).unwrap(),
+            [Answer::Integer(3), Answer::Integer(14)]
+        );
+        assert!(day05.solve(TEST_INPUT, 3).is_err());
+        assert!(day05.solve("not ranges", 1).is_err());
+    }
+
+    #[test]
+    fn test_typed_solution() {
+        use crate::day05::Day05;
+
+        let input = Day05.parse(TEST_INPUT.trim_end()).unwrap();
+        assert_eq!(Day05.part2(&input), Answer::Integer(14));
+    }
+}
@@ -0,0 +1,210 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    time::{Duration, SystemTime},
+};
+
+/// Answers of one run by part, or the reason the part has none.
+pub type Answers = BTreeMap<u8, Result<String, String>>;
+
+/// Modification times of the watched files, `None` for files that do not exist.
+#[derive(Debug, PartialEq, Eq)]
+struct Snapshot(Vec<Option<SystemTime>>);
+
+impl Snapshot {
+    fn take(paths: &[PathBuf]) -> Self {
+        Self(
+            paths
+                .iter()
+                .map(|path| path.metadata().and_then(|m| m.modified()).ok())
+                .collect(),
+        )
+    }
+}
+
+/// Collects the answers from the output of `run --format json`.
+pub fn parse_answers(output: &str) -> Answers {
+    output
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter_map(|record| {
+            let part = record["part"].as_u64()? as u8;
+            let answer = match record["answer"].as_str() {
+                Some(answer) => Ok(answer.to_owned()),
+                None => Err(record["error"]
+                    .as_str()
+                    .or(record["status"].as_str())
+                    .unwrap_or("no answer")
+                    .to_owned()),
+            };
+            Some((part, answer))
+        })
+        .collect()
+}
+
+/// One part of the difference between two runs.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Change<'a> {
+    pub part: u8,
+    pub before: Option<&'a Result<String, String>>,
+    pub after: &'a Result<String, String>,
+}
+
+impl Change<'_> {
+    pub fn changed(&self) -> bool {
+        self.before.is_some_and(|before| before != self.after)
+    }
+}
+
+fn show(answer: &Result<String, String>) -> String {
+    match answer {
+        Ok(answer) => answer.clone(),
+        Err(e) => format!("failed ({})", e.lines().next().unwrap_or_default()),
+    }
+}
+
+impl Display for Change<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.before {
+            Some(before) if self.changed() => write!(
+                f,
+                "\x1b[1;33mPart {}: {} -> {}\x1b[0m",
+                self.part,
+                show(before),
+                show(self.after)
+            ),
+            _ => write!(f, "Part {}: {}", self.part, show(self.after)),
+        }
+    }
+}
+
+/// Pairs every answer of the current run with the one from the previous run.
+pub fn diff<'a>(previous: &'a Answers, current: &'a Answers) -> Vec<Change<'a>> {
+    current
+        .iter()
+        .map(|(&part, after)| Change {
+            part,
+            before: previous.get(&part),
+            after,
+        })
+        .collect()
+}
+
+/// A cargo command without the variables `cargo run` sets for the package being run,
+/// which would otherwise make cargo rebuild when they differ from a plain invocation.
+fn cargo(args: &[&str]) -> Command {
+    let mut command = Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_owned()));
+    for (key, _) in std::env::vars() {
+        if key.starts_with("CARGO_") && !matches!(key.as_str(), "CARGO_HOME" | "CARGO_TARGET_DIR") {
+            command.env_remove(key);
+        }
+    }
+    command.args(args);
+    command
+}
+
+/// Rebuilds, runs the day's tests and its solvers on the real input.
+///
+/// Returns `None` when the build fails, the compiler output is shown instead.
+fn check(day: u8, input_dir: &Path) -> Option<Answers> {
+    if !cargo(&["build", "--quiet"]).status().ok()?.success() {
+        return None;
+    }
+
+    let tests = cargo(&["test", "--quiet", &format!("day{:02}", day)])
+        .stdout(Stdio::null())
+        .status()
+        .ok()?;
+    if tests.success() {
+        println!("Tests passed");
+    } else {
+        println!("\x1b[1;31mTests failed\x1b[0m");
+    }
+
+    let input_dir = input_dir.to_string_lossy();
+    let run = cargo(&["run", "--quiet", "--", "run", &day.to_string()])
+        .args(["--format", "json", "--input-dir", &input_dir])
+        .stderr(Stdio::inherit())
+        .output()
+        .ok()?;
+    Some(parse_answers(&String::from_utf8_lossy(&run.stdout)))
+}
+
+/// Re-checks the day every time one of `paths` changes, until interrupted.
+pub fn watch(day: u8, paths: &[PathBuf], input_dir: &Path) -> ! {
+    let watched: Vec<_> = paths.iter().map(|p| p.display().to_string()).collect();
+    let mut previous = Answers::new();
+    let mut seen = None;
+    loop {
+        let snapshot = Snapshot::take(paths);
+        if seen.as_ref() == Some(&snapshot) {
+            std::thread::sleep(Duration::from_millis(500));
+            continue;
+        }
+        seen = Some(snapshot);
+
+        println!(
+            "\x1b[1mDay {}\x1b[0m, {}",
+            day,
+            jiff::Zoned::now().strftime("%H:%M:%S")
+        );
+        match check(day, input_dir) {
+            Some(answers) => {
+                for change in diff(&previous, &answers) {
+                    println!("{}", change);
+                }
+                previous = answers;
+            }
+            None => println!("\x1b[1;31mBuild failed\x1b[0m"),
+        }
+        println!("Watching {}", watched.join(", "));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    const TEST_INPUT: &str = indoc! {r#"
+        {"day":5,"part":1,"status":"ok","answer":"744","error":null}
+        {"day":5,"part":2,"status":"failed","answer":null,"error":"not yet implemented"}
+    "#};
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = parse_answers(TEST_INPUT);
+        assert_eq!(
+            answers,
+            Answers::from([
+                (1, Ok("744".to_owned())),
+                (2, Err("not yet implemented".to_owned()))
+            ])
+        );
+        let missing = r#"{"day":5,"part":1,"status":"missing-input","answer":null,"error":null}"#;
+        assert_eq!(
+            parse_answers(missing),
+            Answers::from([(1, Err("missing-input".to_owned()))])
+        );
+    }
+
+    #[test]
+    fn test_diff() {
+        let previous = parse_answers(TEST_INPUT);
+        let current = Answers::from([(1, Ok("744".to_owned())), (2, Ok("12".to_owned()))]);
+        let changes = diff(&previous, &current);
+        assert!(!changes[0].changed());
+        assert!(changes[1].changed());
+        assert_eq!(changes[0].to_string(), "Part 1: 744");
+        assert_eq!(
+            changes[1].to_string(),
+            "\x1b[1;33mPart 2: failed (not yet implemented) -> 12\x1b[0m"
+        );
+
+        let none = Answers::new();
+        let first = diff(&none, &current);
+        assert!(first.iter().all(|change| !change.changed()));
+    }
+}